@@ -0,0 +1,48 @@
+//! Compares batch hashing (vectorized where the running CPU supports it) with
+//! hashing the keys one by one.
+//!
+//! Run with `cargo bench --bench batch`. Only the recurrence is vectorized, so
+//! both are about as fast, e.g. on x86-64 with AVX2 (10k keys, scalar vs
+//! batch):
+//!
+//! | `n`       | `k = 7`          | `k = 32`           |
+//! |-----------|------------------|--------------------|
+//! | `2^20`    | 972 µs vs 902 µs | 1.19 ms vs 1.27 ms |
+//! | 1,000,003 | 919 µs vs 855 µs | 1.30 ms vs 1.14 ms |
+
+use {
+    criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput},
+    hash_iter::{BuildHashIterHasher, DoubleHashBuilder, HashIterHasher},
+    std::hint::black_box,
+};
+
+const KEYS: usize = 10_000;
+
+fn batch(c: &mut Criterion) {
+    let keys = (0..KEYS as u64).collect::<Vec<_>>();
+    let mut group = c.benchmark_group("batch");
+    for (name, n) in [("pow2", 1 << 20), ("odd", 1_000_003)] {
+        let hasher = DoubleHashBuilder::<u64>::new()
+            .with_n(n)
+            .build_hash_iter_hasher();
+        for count in [7, 32] {
+            let mut out = vec![0; KEYS * count];
+            let id = format!("{name}/k={count}");
+            group.throughput(Throughput::Elements((KEYS * count) as u64));
+            group.bench_function(BenchmarkId::new("scalar", &id), |b| {
+                b.iter(|| {
+                    for (key, row) in keys.iter().zip(out.chunks_exact_mut(count)) {
+                        hasher.hash_iter_into(black_box(key), row);
+                    }
+                })
+            });
+            group.bench_function(BenchmarkId::new("hash_iter_batch", &id), |b| {
+                b.iter(|| hasher.hash_iter_batch(black_box(&keys), count, &mut out))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, batch);
+criterion_main!(benches);
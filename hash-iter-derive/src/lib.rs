@@ -0,0 +1,88 @@
+//! Derive macro for the `PortableKey` trait of
+//! [`hash-iter`](https://docs.rs/hash-iter), enabled by its `derive` feature.
+
+use {
+    proc_macro::TokenStream,
+    proc_macro2::{Span, TokenStream as TokenStream2},
+    quote::{format_ident, quote},
+    syn::{parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, Index},
+};
+
+/// Derives `PortableKey`, encoding the fields in declaration order.
+///
+/// Enum values are encoded as the index of the variant (a `u32`), followed by
+/// its fields. Type parameters are required to implement `PortableKey`.
+#[proc_macro_derive(PortableKey)]
+pub fn derive_portable_key(input: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(input as DeriveInput);
+    for param in input.generics.type_params_mut() {
+        param.bounds.push(parse_quote!(::hash_iter::PortableKey));
+    }
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (pattern, encode) = fields(&data.fields);
+            quote! {
+                let Self #pattern = self;
+                #encode
+            }
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().enumerate().map(|(index, variant)| {
+                let name = &variant.ident;
+                let index = index as u32;
+                let (pattern, encode) = fields(&variant.fields);
+                quote! {
+                    Self::#name #pattern => {
+                        ::hash_iter::PortableKey::encode(&#index, sink);
+                        #encode
+                    }
+                }
+            });
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Error::new(Span::call_site(), "unions cannot derive `PortableKey`")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
+        impl #impl_generics ::hash_iter::PortableKey for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn encode<S: ::hash_iter::KeySink + ?Sized>(&self, sink: &mut S) {
+                #body
+            }
+        }
+    }
+    .into()
+}
+
+/// Returns the pattern binding the fields, and the code encoding them.
+fn fields(fields: &Fields) -> (TokenStream2, TokenStream2) {
+    let bindings = (0..fields.len())
+        .map(|i| format_ident!("field{}", i))
+        .collect::<Vec<_>>();
+    let pattern = match fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote!({ #(#names: #bindings),* })
+        }
+        Fields::Unnamed(_) => {
+            let indices = (0..fields.len()).map(Index::from);
+            quote!({ #(#indices: #bindings),* })
+        }
+        Fields::Unit => quote!(),
+    };
+    let encode = quote! {
+        #(::hash_iter::PortableKey::encode(#bindings, sink);)*
+    };
+    (pattern, encode)
+}
@@ -0,0 +1,49 @@
+//! AHash hash builders.
+
+use {
+    crate::{sealed, splitmix64, DoubleHashHasher},
+    ::ahash::RandomState,
+};
+
+impl<T> DoubleHashHasher<T, RandomState> {
+    /// Constructs a double hasher, whose starting hash points are computed
+    /// with [AHash](https://crates.io/crates/ahash), keyed by two independent
+    /// states derived from `seed`.
+    ///
+    /// Available with the `ahash` feature. Note that AHash output depends on
+    /// the target (e.g. on AES instructions being available) and on the AHash
+    /// version, so the sequences should not be persisted.
+    ///
+    /// To hash keys exactly as an existing map does, pass (a clone of) its
+    /// [`RandomState`] to [`with_hash_builders`](Self::with_hash_builders):
+    ///
+    /// ```
+    /// use {
+    ///     ahash::RandomState,
+    ///     hash_iter::{DoubleHashHasher, HashIterHasher},
+    ///     std::{collections::HashMap, hash::BuildHasher},
+    /// };
+    ///
+    /// let map = HashMap::<&str, u32, _>::with_hasher(RandomState::with_seeds(1, 2, 3, 4));
+    /// let hasher = DoubleHashHasher::with_hash_builders(
+    ///     map.hasher().clone(),
+    ///     RandomState::with_seeds(5, 6, 7, 8),
+    ///     u64::MAX,
+    /// );
+    /// let first = hasher.hash_iter(&"hello", 3).next();
+    /// assert_eq!(first, Some(map.hasher().hash_one("hello") % u64::MAX));
+    ///
+    /// let hasher = DoubleHashHasher::with_ahash(42, 1000u64);
+    /// assert!(hasher.hash_iter(&"hello", 3).all(|h| h < 1000));
+    /// ```
+    pub fn with_ahash(seed: u64, n: T) -> Self
+    where
+        T: sealed::Modulus,
+    {
+        let state = |i: u64| {
+            let [k0, k1, k2, k3] = [0, 1, 2, 3].map(|j| splitmix64(seed, 4 * i + j));
+            RandomState::with_seeds(k0, k1, k2, k3)
+        };
+        Self::with_hash_builders(state(0), state(1), n)
+    }
+}
@@ -0,0 +1,165 @@
+//! Arbitrary-precision hash sequences, available with the `num-bigint`
+//! feature.
+
+use {
+    crate::{DoubleHashHasher, HashIterHasher, HashingMode, Reduction},
+    num_bigint::BigUint,
+    num_traits::Zero,
+    std::{hash, iter},
+};
+
+impl<H1, H2> HashIterHasher<BigUint> for DoubleHashHasher<BigUint, H1, H2>
+where
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    fn hash_iter<K: hash::Hash + ?Sized>(
+        &self,
+        key: &K,
+        count: usize,
+    ) -> impl Iterator<Item = BigUint> {
+        self.hash_iter_u64(key, count as u64)
+    }
+
+    fn hash_iter_u64<K: hash::Hash + ?Sized>(
+        &self,
+        key: &K,
+        count: u64,
+    ) -> impl Iterator<Item = BigUint> {
+        // Use as many 64-bit hash words as required to cover `[0, n)`.
+        let words = self.n.bits().div_ceil(64).max(1) as usize;
+        let key_words = self.key_words(key);
+        let reduce = |v: BigUint, n: &BigUint| match self.reduction {
+            // The start points are `64 * words` bits wide.
+            Reduction::Lemire => (v * n) >> (64 * words),
+            // Masking is the same as the remainder, for power-of-two `n`.
+            _ => v % n,
+        };
+        let x = reduce(from_hash_words(words, |i| key_words.word1(i)), &self.n);
+        let mut y = from_hash_words(words, |i| key_words.word2(i));
+        let pow2 = self.n.count_ones() == 1;
+        let masked = matches!(self.reduction, Reduction::BitMask | Reduction::Auto) && pow2;
+        if masked && self.n > BigUint::from(1u8) {
+            // Same as for fixed-width types: `y` is made odd.
+            y %= &self.n;
+            if !y.bit(0) {
+                y += 1u8;
+            }
+        } else if self.nonzero_h2 && self.n > BigUint::from(1u8) {
+            // Same as for fixed-width types: `y` is mapped into `[1, n)`, and
+            // made odd if `n` is a power of two.
+            y = 1u8 + reduce(y, &(&self.n - 1u8));
+            if !y.bit(0) && pow2 {
+                y -= 1u8;
+            }
+        } else {
+            y = reduce(y, &self.n);
+        }
+        BigHashes::new(x, y, self.n.clone(), count).with_hashing_mode(self.hashing_mode)
+    }
+}
+
+/// Builds a big integer out of `words` 64-bit hash words (little-endian).
+fn from_hash_words(words: usize, mut word: impl FnMut(usize) -> u64) -> BigUint {
+    let bytes = (0..words)
+        .flat_map(|i| word(i).to_le_bytes())
+        .collect::<Vec<_>>();
+    BigUint::from_bytes_le(&bytes)
+}
+
+/// Iterator over arbitrary-precision hash values generated using enhanced
+/// double hashing technique.
+///
+/// Unlike [`Hashes`](crate::Hashes), computations never wrap, so the sequence
+/// matches the mathematical definition exactly:
+/// ```math
+/// h(i) = h1(k) + i * h2(k) + (i^3-i)/6 (mod n)
+/// ```
+#[derive(Debug, Clone)]
+pub struct BigHashes {
+    /// The first hash point.
+    hash1: BigUint,
+
+    /// The second hash point.
+    hash2: BigUint,
+
+    /// The size of the hash table.
+    n: BigUint,
+
+    /// The number of hash points to generate.
+    k: u64,
+
+    /// The current number of hash points generated.
+    cnt: u64,
+
+    /// The recurrence used to generate hash points.
+    mode: HashingMode,
+}
+
+impl BigHashes {
+    /// Constructs a new hash iterator.
+    ///
+    /// The iterator is configured with the given starting hash points, for the
+    /// hashmap of size `n` (which must be non-zero), with expected number of
+    /// generated hash points equal to `k`.
+    pub fn new(hash1: BigUint, hash2: BigUint, n: BigUint, k: u64) -> Self {
+        assert!(!n.is_zero(), "n must be non-zero");
+        Self {
+            hash1: hash1 % &n,
+            hash2: hash2 % &n,
+            n,
+            k,
+            cnt: 0,
+            mode: HashingMode::default(),
+        }
+    }
+
+    /// Sets the recurrence used to generate hash points (enhanced by
+    /// default).
+    ///
+    /// Must be called before the iteration starts.
+    pub fn with_hashing_mode(self, mode: HashingMode) -> Self {
+        assert_eq!(self.cnt, 0, "iteration has already started");
+        let hash2 = match mode {
+            HashingMode::Linear { stride } => BigUint::from(stride) % &self.n,
+            _ => self.hash2,
+        };
+        Self {
+            hash2,
+            mode,
+            ..self
+        }
+    }
+}
+
+impl Iterator for BigHashes {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cnt == self.k {
+            return None;
+        }
+
+        if self.cnt == 0 {
+            self.cnt += 1;
+            return Some(self.hash1.clone());
+        }
+
+        self.hash1 = (&self.hash1 + &self.hash2) % &self.n;
+        if self.mode == HashingMode::Enhanced {
+            self.hash2 = (&self.hash2 + self.cnt) % &self.n;
+        }
+        self.cnt += 1;
+
+        Some(self.hash1.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.k - self.cnt) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl iter::FusedIterator for BigHashes {}
@@ -0,0 +1,72 @@
+//! Helpers for setting and testing Bloom filter bits in a `FixedBitSet`,
+//! available with the `fixed-bitset` feature.
+
+use {
+    crate::{DoubleHashHasher, Number},
+    fixedbitset::FixedBitSet,
+    std::hash,
+};
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Sets the `count` bits of the given key in `bitset`.
+    ///
+    /// Returns `true` if all the bits were already set (i.e. the key was
+    /// possibly present before the insertion).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` exceeds the length of `bitset`, or is the full range
+    /// (`n = 0`).
+    pub fn bitset_insert<K>(&self, key: &K, count: usize, bitset: &mut FixedBitSet) -> bool
+    where
+        K: hash::Hash + ?Sized,
+    {
+        self.check_bitset_len(bitset);
+        let mut present = true;
+        self.hashes(key, count as u64).visit(|idx| {
+            // SAFETY: hash values are always in `[0, n)`, and `n` is non-zero
+            // and does not exceed the length of the bitset.
+            present &= unsafe { bitset.put_unchecked(to_index(idx)) };
+        });
+        present
+    }
+
+    /// Tests whether all the `count` bits of the given key are set in
+    /// `bitset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` exceeds the length of `bitset`, or is the full range
+    /// (`n = 0`).
+    pub fn bitset_contains<K>(&self, key: &K, count: usize, bitset: &FixedBitSet) -> bool
+    where
+        K: hash::Hash + ?Sized,
+    {
+        self.check_bitset_len(bitset);
+        self.hashes(key, count as u64).all(|idx| {
+            // SAFETY: hash values are always in `[0, n)`, and `n` is non-zero
+            // and does not exceed the length of the bitset.
+            unsafe { bitset.contains_unchecked(to_index(idx)) }
+        })
+    }
+
+    /// Checks that all the hash values index into `bitset`, which the unchecked
+    /// bit accesses rely upon.
+    fn check_bitset_len(&self, bitset: &FixedBitSet) {
+        // The full range sentinel would let hash values span the whole type.
+        assert!(!self.n.is_zero(), "n must not be the full range");
+        let fits = self.n.to_usize().is_some_and(|n| n <= bitset.len());
+        assert!(fits, "n must not exceed the length of the bitset");
+    }
+}
+
+#[inline]
+fn to_index<T: Number>(idx: T) -> usize {
+    // Cannot fail: `idx < n`, and `n` has already been checked to fit `usize`.
+    idx.to_usize().unwrap_or_default()
+}
@@ -0,0 +1,96 @@
+//! Keyed BLAKE3 hash builders, for collision resistance against adversaries.
+
+use {
+    crate::{sealed, DoubleHashHasher},
+    std::{fmt, hash},
+};
+
+/// Derivation contexts of the keys of the two start points, see
+/// [`blake3::derive_key`].
+const CONTEXTS: [&str; 2] = [
+    "hash-iter 2026-10-16 first start point",
+    "hash-iter 2026-10-16 second start point",
+];
+
+/// Builder of keyed BLAKE3 hashers.
+///
+/// BLAKE3 is a cryptographic hash function: keyed with a secret 256-bit key,
+/// neither collisions nor hash values of chosen keys can be feasibly
+/// computed by an adversary. The key is not shown by the [`Debug`]
+/// implementation.
+///
+/// Available with the `blake3` feature. See
+/// [`DoubleHashHasher::with_blake3`] for a ready-made double hasher.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Blake3Builder {
+    key: [u8; 32],
+}
+
+impl Blake3Builder {
+    /// Constructs a new builder, with the given secret key.
+    pub fn new(key: [u8; 32]) -> Self {
+        Self { key }
+    }
+}
+
+impl fmt::Debug for Blake3Builder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Blake3Builder").finish_non_exhaustive()
+    }
+}
+
+impl hash::BuildHasher for Blake3Builder {
+    type Hasher = Blake3Hasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Blake3Hasher(::blake3::Hasher::new_keyed(&self.key))
+    }
+}
+
+/// Keyed BLAKE3 hasher, see [`Blake3Builder`].
+///
+/// The hash value is the first 8 bytes of the output, read as a
+/// little-endian integer.
+#[derive(Clone)]
+pub struct Blake3Hasher(::blake3::Hasher);
+
+impl fmt::Debug for Blake3Hasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Blake3Hasher").finish_non_exhaustive()
+    }
+}
+
+impl hash::Hasher for Blake3Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let hash = self.0.finalize();
+        let (word, _) = hash.as_bytes().split_first_chunk().unwrap();
+        u64::from_le_bytes(*word)
+    }
+}
+
+impl<T> DoubleHashHasher<T, Blake3Builder> {
+    /// Constructs a double hasher, whose starting hash points are computed
+    /// with keyed BLAKE3.
+    ///
+    /// The keys of the two start points are derived from the secret `key`
+    /// (with [`blake3::derive_key`], using distinct contexts), so that the
+    /// start points are independent.
+    ///
+    /// ```
+    /// use hash_iter::{DoubleHashHasher, HashIterHasher};
+    ///
+    /// let hasher = DoubleHashHasher::with_blake3([42; 32], 1000u64);
+    /// assert!(hasher.hash_iter(&"hello", 3).all(|h| h < 1000));
+    /// ```
+    pub fn with_blake3(key: [u8; 32], n: T) -> Self
+    where
+        T: sealed::Modulus,
+    {
+        let [key1, key2] = CONTEXTS.map(|context| ::blake3::derive_key(context, &key));
+        Self::with_hash_builders(Blake3Builder::new(key1), Blake3Builder::new(key2), n)
+    }
+}
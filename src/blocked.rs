@@ -0,0 +1,50 @@
+//! Cache-local hash sequences, for blocked Bloom filters.
+
+use {
+    crate::{rem, DoubleHashHasher, Number},
+    num_traits::{Bounded, One, WrappingSub, Zero},
+    std::hash,
+};
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Returns an iterator over `count` hash values for the given key, all of
+    /// which fall into the same block of `block_size` consecutive slots.
+    ///
+    /// The table is split into `n / block_size` blocks (if `n` is not a
+    /// multiple of `block_size`, the trailing slots are never used). The first
+    /// hash value selects the block (and is the first emitted value), while
+    /// the remaining ones are offsets within that block, generated by the
+    /// regular sequence modulo `block_size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` is zero, or exceeds `n` (`2^BITS` for the full
+    /// range).
+    pub fn blocked_hashes<K>(&self, key: &K, count: u64, block_size: T) -> impl Iterator<Item = T>
+    where
+        K: hash::Hash + ?Sized,
+    {
+        let n = self.n.to_repr();
+        let size = block_size.to_repr();
+        let fits = !size.is_zero() && (size <= n || n.is_zero());
+        assert!(fits, "block_size must be in [1, n]");
+
+        // Slots covered by whole blocks (`0` for the whole range).
+        let tail = if n.is_zero() {
+            (T::Repr::max_value() % size + T::Repr::one()) % size
+        } else {
+            n % size
+        };
+        let (x, y) = self.base_hashes(key);
+        // Start of the block: the first hash value, rounded down to a multiple
+        // of the block size.
+        let block = rem(x, n.wrapping_sub(&tail)) / size * size;
+        self.sequence::<T>(x, y, size, count)
+            .map(move |offset| T::from_repr(block + offset.to_repr()))
+    }
+}
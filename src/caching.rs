@@ -0,0 +1,77 @@
+//! Hasher wrapper memoizing the base hashes of the last key.
+
+use {
+    crate::{DoubleHashHasher, Hashes, Number},
+    std::{cell::RefCell, hash},
+    xxhash_rust::xxh3::Xxh3Builder,
+};
+
+/// Wraps a [`DoubleHashHasher`], remembering the two base hashes of the last
+/// hashed key.
+///
+/// Repeated requests for the same key (e.g. checking whether a key is present
+/// before inserting it) then only reduce the cached hashes, instead of hashing
+/// the key twice again. Only the last key is cached, so interleaving requests
+/// for different keys gains nothing.
+///
+/// The cache is behind a `RefCell`, so that it can be updated through a shared
+/// reference: the wrapper is therefore not `Sync`.
+#[derive(Clone)]
+pub struct CachingHashIterHasher<K, T: Number = u64, H1 = Xxh3Builder, H2 = H1> {
+    hasher: DoubleHashHasher<T, H1, H2>,
+    last: RefCell<Option<Entry<K, T>>>,
+}
+
+/// The last hashed key, along with its base hashes.
+type Entry<K, T> = (K, <T as Number>::Repr, <T as Number>::Repr);
+
+impl<K, T, H1, H2> CachingHashIterHasher<K, T, H1, H2>
+where
+    K: hash::Hash + Eq + Clone,
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Wraps the given hasher, with an empty cache.
+    pub fn new(hasher: DoubleHashHasher<T, H1, H2>) -> Self {
+        Self {
+            hasher,
+            last: RefCell::new(None),
+        }
+    }
+
+    /// Returns an iterator over `count` hash values for the given key.
+    ///
+    /// Same as [`DoubleHashHasher::hashes`], but the key is only hashed if it
+    /// differs from the last one.
+    pub fn hashes(&self, key: &K, count: u64) -> Hashes<T> {
+        let mut last = self.last.borrow_mut();
+        let (x, y) = match &*last {
+            Some((last_key, x, y)) if last_key == key => (*x, *y),
+            _ => {
+                let (x, y) = self.hasher.base_hashes(key);
+                *last = Some((key.clone(), x, y));
+                (x, y)
+            }
+        };
+        self.hasher.sequence(x, y, self.hasher.n.to_repr(), count)
+    }
+
+    /// Returns an iterator over `count` hash values for the given key.
+    ///
+    /// Same as [`hashes`](Self::hashes), but takes a `usize` count, mirroring
+    /// [`HashIterHasher::hash_iter`](crate::HashIterHasher::hash_iter).
+    pub fn hash_iter(&self, key: &K, count: usize) -> impl Iterator<Item = T> {
+        self.hashes(key, count as u64)
+    }
+
+    /// Forgets the cached hashes.
+    pub fn clear(&self) {
+        self.last.take();
+    }
+
+    /// Returns the wrapped hasher.
+    pub fn into_inner(self) -> DoubleHashHasher<T, H1, H2> {
+        self.hasher
+    }
+}
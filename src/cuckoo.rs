@@ -0,0 +1,94 @@
+//! Fingerprints and bucket indices for cuckoo filters.
+
+use {
+    crate::{mix64, DoubleHashHasher},
+    std::hash,
+};
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Returns the fingerprint and the two candidate buckets of the given key
+    /// in a cuckoo filter of `buckets` buckets.
+    ///
+    /// See [`CuckooHash`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buckets` is not a power of two, or if `fingerprint_bits` is
+    /// not in `[1, 64]`.
+    pub fn cuckoo<K: hash::Hash + ?Sized>(
+        &self,
+        key: &K,
+        buckets: usize,
+        fingerprint_bits: u32,
+    ) -> CuckooHash {
+        let words = self.key_words(key);
+        CuckooHash::new(words.word1(0), words.word2(0), buckets, fingerprint_bits)
+    }
+}
+
+/// Fingerprint of a key, and its two candidate buckets in a cuckoo filter
+/// with power-of-two number of buckets.
+///
+/// The first bucket comes from the key's first hash word, and the fingerprint
+/// from the high bits of its second one (zero fingerprints are mapped to 1, so
+/// that zero can mark empty entries). The alternate bucket only depends on the
+/// current bucket and the fingerprint (partial-key cuckoo hashing): it is
+/// `index1 ^ hash(fingerprint)`, so that entries can be relocated without
+/// access to their keys, see [`CuckooHash::alt_index`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CuckooHash {
+    fingerprint: u64,
+    index1: usize,
+    index2: usize,
+}
+
+impl CuckooHash {
+    /// Constructs the fingerprint and bucket indices out of the two hash words
+    /// of a key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buckets` is not a power of two, or if `fingerprint_bits` is
+    /// not in `[1, 64]`.
+    pub fn new(hash1: u64, hash2: u64, buckets: usize, fingerprint_bits: u32) -> Self {
+        assert!(buckets.is_power_of_two(), "buckets must be a power of two");
+        assert!(
+            (1..=64).contains(&fingerprint_bits),
+            "fingerprint_bits must be in [1, 64]"
+        );
+        let fingerprint = (hash2 >> (64 - fingerprint_bits)).max(1);
+        let index1 = hash1 as usize & (buckets - 1);
+        Self {
+            fingerprint,
+            index1,
+            index2: Self::alt_index(index1, fingerprint, buckets),
+        }
+    }
+
+    /// Returns the fingerprint, which is never zero.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// Returns the first candidate bucket.
+    pub fn index1(&self) -> usize {
+        self.index1
+    }
+
+    /// Returns the second candidate bucket.
+    pub fn index2(&self) -> usize {
+        self.index2
+    }
+
+    /// Returns the other candidate bucket of an entry with the given
+    /// fingerprint, stored in bucket `index`.
+    ///
+    /// This is an involution: `alt_index(alt_index(i, fp, b), fp, b) == i`.
+    pub fn alt_index(index: usize, fingerprint: u64, buckets: usize) -> usize {
+        index ^ (mix64(fingerprint) as usize & (buckets - 1))
+    }
+}
@@ -0,0 +1,35 @@
+//! Helpers shared by the cryptographic digest backends.
+
+/// Half of a 256-bit digest, out of which a hash value is taken.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DigestHalf {
+    /// Bytes `0..16` of the digest.
+    #[default]
+    First,
+
+    /// Bytes `16..32` of the digest.
+    Second,
+}
+
+impl DigestHalf {
+    /// Returns the leading 8 bytes of the half, read as a big-endian integer.
+    pub(crate) fn word(self, digest: &[u8; 32]) -> u64 {
+        let offset = match self {
+            Self::First => 0,
+            Self::Second => 16,
+        };
+        let (word, _) = digest[offset..].split_first_chunk().unwrap();
+        u64::from_be_bytes(*word)
+    }
+}
+
+/// Returns the words of both halves of the digest (see [`DigestHalf::word`]),
+/// as the low and the high halves, so that a single digest provides both
+/// start points.
+pub(crate) fn digest_words(digest: &[u8; 32]) -> u128 {
+    let (first, second) = (
+        DigestHalf::First.word(digest),
+        DigestHalf::Second.word(digest),
+    );
+    first as u128 | (second as u128) << 64
+}
@@ -0,0 +1,127 @@
+//! Sequences of pairwise distinct hash values.
+
+use {
+    crate::{add_mod, slot_count, DoubleHashHasher, Hashes, Number},
+    num_traits::One,
+    std::{collections::BTreeSet, hash},
+};
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Returns an iterator over `count` pairwise distinct hash values for the
+    /// given key.
+    ///
+    /// Duplicate hash points are skipped, and the sequence is extended as
+    /// needed. Since the sequence is periodic modulo `n`, it is not guaranteed
+    /// to ever produce `count` distinct values: so, once `n` duplicates have
+    /// been skipped, the remaining values are found by linear probing (i.e.
+    /// trying `h + 1`, `h + 2`, etc). Therefore, at most `count + n` hash
+    /// points are drawn from the sequence, and each of the remaining values
+    /// requires at most `n` probes. When `count` is small compared to `n`,
+    /// duplicates are rare, and the overhead is negligible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` exceeds `n` (`2^BITS` for the full range).
+    pub fn hash_iter_distinct<K: hash::Hash + ?Sized>(
+        &self,
+        key: &K,
+        count: usize,
+    ) -> impl Iterator<Item = T> {
+        let fits = count as u64 <= slot_count(self.n.to_repr());
+        assert!(fits, "count must not exceed n");
+        self.distinct_hashes(key, count as u64)
+    }
+
+    /// Returns an iterator over `count` hash values for the given key, the
+    /// first `min(count, n)` of which are pairwise distinct.
+    ///
+    /// Values past the first `n` are drawn from the regular sequence.
+    pub(crate) fn distinct_hashes<K: hash::Hash + ?Sized>(
+        &self,
+        key: &K,
+        count: u64,
+    ) -> Distinct<T> {
+        Distinct::new(self.hashes(key, u64::MAX), count)
+    }
+}
+
+/// Iterator over distinct hash values.
+#[derive(Debug, Clone)]
+pub(crate) struct Distinct<T: Number> {
+    /// The underlying (unbounded) sequence.
+    hashes: Hashes<T>,
+
+    /// Hash values generated so far.
+    seen: BTreeSet<T::Repr>,
+
+    /// The number of hash values still to be generated.
+    remaining: u64,
+
+    /// The number of distinct hash values still to be generated.
+    unique: u64,
+
+    /// The number of duplicates that can still be skipped, before falling back
+    /// to linear probing.
+    budget: u64,
+
+    /// The last hash value considered.
+    last: T::Repr,
+}
+
+impl<T: Number> Distinct<T> {
+    /// Draws `count` hash values out of the given (unbounded) sequence, the
+    /// first `min(count, n)` of which are pairwise distinct (`n = 0` stands
+    /// for `2^BITS`).
+    pub(crate) fn new(hashes: Hashes<T>, count: u64) -> Self {
+        let n = hashes.n;
+        let slots = slot_count(n);
+        Self {
+            hashes,
+            seen: BTreeSet::new(),
+            remaining: count,
+            unique: count.min(slots),
+            budget: slots,
+            last: n,
+        }
+    }
+}
+
+impl<T: Number> Iterator for Distinct<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        // All the slots are taken, no more distinct values are possible.
+        if self.unique == 0 {
+            return self.hashes.next();
+        }
+        self.unique -= 1;
+
+        loop {
+            self.last = match self.budget {
+                0 => add_mod(self.last, T::Repr::one(), self.hashes.n),
+                _ => self.hashes.next().expect("unbounded sequence").to_repr(),
+            };
+            if self.seen.insert(self.last) {
+                return Some(T::from_repr(self.last));
+            }
+            self.budget = self.budget.saturating_sub(1);
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Families of independently seeded hash functions.
+
+use {
+    crate::{hash_word, rem, splitmix64, HashIterHasher, Number, TruncationMode},
+    std::hash,
+    xxhash_rust::xxh3::Xxh3Builder,
+};
+
+/// Family of independently seeded XXH3 hash functions.
+///
+/// An alternative to [`DoubleHashHasher`](crate::DoubleHashHasher): the `i`-th
+/// emitted value is the key's hash under the `i`-th member of the family
+/// (whose seed is derived from the master seed), reduced modulo `n`. This
+/// costs a full hash of the key per value, but the values do not depend on
+/// each other, which is useful as a baseline when evaluating double hashing,
+/// or when the analysis requires independent hash functions.
+#[derive(Debug, Clone, Copy)]
+pub struct HashFamily<T = u64> {
+    seed: u64,
+    n: T,
+    truncation_mode: TruncationMode,
+}
+
+impl<T: Number> HashFamily<T> {
+    /// Constructs a new hash family, with the default master seed.
+    ///
+    /// As with [`DoubleHashBuilder`](crate::DoubleHashBuilder), `n` defaults
+    /// to `usize::MAX`, capped at the maximum value of `T`.
+    pub fn new() -> Self {
+        Self {
+            seed: 0,
+            n: T::from_u64(usize::MAX as u64).unwrap_or_else(T::max_value),
+            truncation_mode: TruncationMode::default(),
+        }
+    }
+
+    /// Sets the master seed, out of which the seeds of all members are
+    /// derived.
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+
+    /// Sets the modulus of the emitted hash values (`0` stands for the whole
+    /// range of the output type).
+    pub fn with_n(self, n: T) -> Self {
+        Self { n, ..self }
+    }
+
+    /// Sets the strategy for converting 64-bit hashes into narrower types.
+    pub fn with_truncation_mode(self, truncation_mode: TruncationMode) -> Self {
+        Self {
+            truncation_mode,
+            ..self
+        }
+    }
+
+    /// Returns the hash builder of the `i`-th member of the family.
+    pub fn member(&self, i: u64) -> Xxh3Builder {
+        Xxh3Builder::new().with_seed(splitmix64(self.seed, i))
+    }
+
+    /// Returns the hash value of the key under the `i`-th member of the
+    /// family, i.e. the `i`-th value emitted by
+    /// [`hash_iter`](HashIterHasher::hash_iter).
+    pub fn hash<K: hash::Hash + ?Sized>(&self, key: &K, i: u64) -> T {
+        let member = self.member(i);
+        let mode = self.truncation_mode;
+        let hash = T::Repr::from_hash_words(|j| mode.apply::<T::Repr>(hash_word(&member, key, j)));
+        T::from_repr(rem(hash, self.n.to_repr()))
+    }
+}
+
+impl<T: Number> Default for HashFamily<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Number> HashIterHasher<T> for HashFamily<T> {
+    fn hash_iter<K: hash::Hash + ?Sized>(&self, key: &K, count: usize) -> impl Iterator<Item = T> {
+        (0..count as u64).map(move |i| self.hash(key, i))
+    }
+}
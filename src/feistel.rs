@@ -0,0 +1,150 @@
+//! Keyed permutations of `[0, 2^b)`, built out of a Feistel network.
+
+use {
+    crate::{mix64, DoubleHashHasher},
+    std::hash,
+};
+
+/// The number of Feistel rounds (four rounds make a strong pseudorandom
+/// permutation, given a pseudorandom round function).
+const ROUNDS: usize = 4;
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Returns the permutation of `[0, 2^bits)` keyed by the given key.
+    ///
+    /// Unlike double hashing, the permutation never repeats an index (within
+    /// the first `2^bits` ones), and it is reversible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` exceeds `64`.
+    pub fn feistel_permutation<K>(&self, key: &K, bits: u32) -> FeistelPermutation
+    where
+        K: hash::Hash + ?Sized,
+    {
+        let words = self.key_words(key);
+        FeistelPermutation::new(bits, words.word1(0), words.word2(0))
+    }
+}
+
+/// Keyed pseudorandom permutation of `[0, 2^bits)`.
+///
+/// The domain is split into two halves of (at most) 32 bits, which go through
+/// a balanced Feistel network. When `bits` is odd, the network operates on
+/// `[0, 2^(bits+1))`, and values outside of the domain are mapped again
+/// (cycle walking), which takes two rounds of the network on average.
+#[derive(Debug, Clone)]
+pub struct FeistelPermutation {
+    /// The width of the domain.
+    bits: u32,
+
+    /// The width of each half of the (even-width) network.
+    half: u32,
+
+    /// Per-round keys.
+    keys: [u64; ROUNDS],
+}
+
+impl FeistelPermutation {
+    /// Constructs the permutation of `[0, 2^bits)` keyed by the two given
+    /// (hash) values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` exceeds `64`.
+    pub fn new(bits: u32, key1: u64, key2: u64) -> Self {
+        assert!(bits <= 64, "bits must not exceed 64");
+        let mut round = 0;
+        let keys = [(); ROUNDS].map(|_| {
+            round += 1;
+            mix64(key1 ^ mix64(key2.wrapping_add(round)))
+        });
+        Self {
+            bits,
+            half: bits.div_ceil(2),
+            keys,
+        }
+    }
+
+    /// Returns the width of the domain.
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Maps `x` to its image under the permutation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is outside of `[0, 2^bits)`.
+    pub fn permute(&self, x: u64) -> u64 {
+        self.check(x);
+        let mut y = self.encrypt(x);
+        while !self.contains(y) {
+            y = self.encrypt(y);
+        }
+        y
+    }
+
+    /// Maps `y` back to its preimage, i.e. `invert(permute(x)) == x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is outside of `[0, 2^bits)`.
+    pub fn invert(&self, y: u64) -> u64 {
+        self.check(y);
+        let mut x = self.decrypt(y);
+        while !self.contains(x) {
+            x = self.decrypt(x);
+        }
+        x
+    }
+
+    /// Returns an iterator over the images of `0, 1, 2, ...`, i.e. over the
+    /// first `count` indices of the permuted domain (or the whole domain, if
+    /// it is smaller).
+    pub fn iter(&self, count: u64) -> impl Iterator<Item = u64> + '_ {
+        let count = match self.bits {
+            64 => count,
+            bits => count.min(1 << bits),
+        };
+        (0..count).map(|x| self.permute(x))
+    }
+
+    fn check(&self, x: u64) {
+        assert!(self.contains(x), "value is outside of the domain");
+    }
+
+    fn contains(&self, x: u64) -> bool {
+        self.bits == 64 || x >> self.bits == 0
+    }
+
+    /// Applies the Feistel network.
+    fn encrypt(&self, x: u64) -> u64 {
+        let (mut l, mut r) = (x >> self.half, x & self.mask());
+        for key in self.keys {
+            (l, r) = (r, l ^ self.round(r, key));
+        }
+        (l << self.half) | r
+    }
+
+    /// Applies the Feistel network in reverse.
+    fn decrypt(&self, y: u64) -> u64 {
+        let (mut l, mut r) = (y >> self.half, y & self.mask());
+        for key in self.keys.into_iter().rev() {
+            (l, r) = (r ^ self.round(l, key), l);
+        }
+        (l << self.half) | r
+    }
+
+    fn round(&self, v: u64, key: u64) -> u64 {
+        mix64(v ^ key) & self.mask()
+    }
+
+    fn mask(&self) -> u64 {
+        (1 << self.half) - 1
+    }
+}
@@ -0,0 +1,56 @@
+//! Segment triples, for xor and binary fuse filters.
+
+use {
+    crate::{DoubleHashHasher, Number},
+    num_traits::{Bounded, One, Zero},
+    std::hash,
+};
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Returns the three positions of the given key in a fuse filter
+    /// consisting of `segment_count + 2` segments of `segment_length` slots.
+    ///
+    /// The first position is uniform over the first `segment_count` segments,
+    /// and the other two are in the two segments following it. Offsets within
+    /// the segments are the first three values of the regular sequence modulo
+    /// `segment_length`. The hasher's `n` is ignored, the filter's array must
+    /// hold `(segment_count + 2) * segment_length` slots.
+    ///
+    /// With a `segment_count` of 1, these are the positions of a classic xor
+    /// filter (one in each third of the array).
+    ///
+    /// # Panics
+    ///
+    /// Panics if either of the sizes is zero, or if the array length
+    /// overflows the output type.
+    pub fn fuse_triple<K>(&self, key: &K, segment_length: T, segment_count: T) -> [T; 3]
+    where
+        K: hash::Hash + ?Sized,
+    {
+        let (len, segments) = (segment_length.to_repr(), segment_count.to_repr());
+        assert!(
+            !len.is_zero() && !segments.is_zero(),
+            "sizes must be non-zero"
+        );
+        let two = T::Repr::one() + T::Repr::one();
+        assert!(
+            segments <= T::Repr::max_value() - two && segments + two <= T::Repr::max_value() / len,
+            "fuse filter is too large"
+        );
+
+        let (x, y) = self.base_hashes(key);
+        let h0 = x % (segments * len);
+        let start = h0 - h0 % len;
+        let mut offsets = self.sequence::<T>(x, y, len, 3).skip(1);
+        let mut next =
+            |segment: T::Repr| segment + offsets.next().expect("sequence is too short").to_repr();
+        let h1 = next(start + len);
+        let h2 = next(start + len + len);
+        [h0, h1, h2].map(T::from_repr)
+    }
+}
@@ -0,0 +1,133 @@
+//! HMAC-SHA-256 hash builders, keyed by a secret.
+
+use {
+    crate::{digest::digest_words, sealed, DigestHalf, DoubleHashHasher},
+    hmac::{Hmac, Mac},
+    sha2::Sha256,
+    std::{fmt, hash},
+    zeroize::Zeroizing,
+};
+
+/// Builder of [`HmacHasher`]s, computing HMAC-SHA-256 of the keys under a
+/// secret key.
+///
+/// As long as the secret key is not known, the hash values (and so the probe
+/// positions) of the keys cannot be predicted, even by an adversary observing
+/// the behavior of the filter. The secret key is zeroized when the builder is
+/// dropped (or when it is replaced), and it is not shown by the [`Debug`]
+/// implementation.
+///
+/// Available with the `hmac` feature. See [`DoubleHashHasher::with_hmac`] for
+/// a ready-made double hasher.
+#[derive(Clone, Default)]
+pub struct HmacBuilder {
+    secret_key: Zeroizing<Vec<u8>>,
+    half: DigestHalf,
+}
+
+impl HmacBuilder {
+    /// Constructs a new builder, with an empty secret key (which provides no
+    /// protection, see [`with_secret_key`](Self::with_secret_key)), taking hash
+    /// values out of the first half of the MAC.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the secret key, which can be of any length (32 random bytes are
+    /// recommended).
+    pub fn with_secret_key(self, secret_key: &[u8]) -> Self {
+        Self {
+            secret_key: Zeroizing::new(secret_key.to_vec()),
+            ..self
+        }
+    }
+
+    /// Sets the half of the MAC the hash values are taken out of.
+    pub fn with_half(self, half: DigestHalf) -> Self {
+        Self { half, ..self }
+    }
+}
+
+impl fmt::Debug for HmacBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HmacBuilder")
+            .field("half", &self.half)
+            .finish_non_exhaustive()
+    }
+}
+
+impl hash::BuildHasher for HmacBuilder {
+    type Hasher = HmacHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        HmacHasher {
+            mac: Hmac::new_from_slice(&self.secret_key).expect("HMAC accepts keys of any length"),
+            half: self.half,
+        }
+    }
+}
+
+/// HMAC-SHA-256 hasher, see [`HmacBuilder`].
+///
+/// The hash value is the leading 8 bytes of the configured half of the MAC,
+/// read as a big-endian integer.
+#[derive(Clone)]
+pub struct HmacHasher {
+    mac: Hmac<Sha256>,
+    half: DigestHalf,
+}
+
+impl HmacHasher {
+    /// Returns the leading words of both halves of the MAC, as the low and the
+    /// high halves (see [`digest_words`]).
+    pub(crate) fn digest_words(&self) -> u128 {
+        digest_words(&self.mac.clone().finalize().into_bytes().into())
+    }
+}
+
+impl fmt::Debug for HmacHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HmacHasher")
+            .field("half", &self.half)
+            .finish_non_exhaustive()
+    }
+}
+
+impl hash::Hasher for HmacHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.mac.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        self.half
+            .word(&self.mac.clone().finalize().into_bytes().into())
+    }
+}
+
+impl<T> DoubleHashHasher<T, HmacBuilder> {
+    /// Constructs a double hasher, whose starting hash points are taken out of
+    /// the two halves of HMAC-SHA-256 of the key, under the given secret key.
+    ///
+    /// The MAC is computed once per key (except for
+    /// [`stream_hasher`](Self::stream_hasher), which computes each half
+    /// separately).
+    ///
+    /// ```
+    /// use hash_iter::{DoubleHashHasher, HashIterHasher};
+    ///
+    /// let hasher = DoubleHashHasher::with_hmac(b"0123456789abcdef0123456789abcdef", 1000u64);
+    /// assert!(hasher.hash_iter(&"hello", 3).all(|h| h < 1000));
+    /// ```
+    pub fn with_hmac(secret_key: &[u8], n: T) -> Self
+    where
+        T: sealed::Modulus,
+    {
+        let builder = HmacBuilder::new().with_secret_key(secret_key);
+        let second = builder.clone().with_half(DigestHalf::Second);
+        Self::with_hash_builders(builder, second, n).with_split_hash(|builder, feed| {
+            let mut hasher = hash::BuildHasher::build_hasher(builder);
+            feed(&mut hasher);
+            hasher.digest_words()
+        })
+    }
+}
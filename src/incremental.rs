@@ -0,0 +1,241 @@
+//! Incremental hashing of keys fed in parts, without buffering them.
+
+#[cfg(any(feature = "keccak", feature = "sha2"))]
+use std::hash::BuildHasher;
+
+use {
+    crate::{
+        distinct::Distinct,
+        mix64,
+        splitmix64,
+        DoubleHashHasher,
+        HashIter,
+        IntegerHasher,
+        KeyHashing,
+        Number,
+    },
+    std::hash::{self, Hash, Hasher},
+    xxhash_rust::xxh3::Xxh3,
+};
+
+#[cfg(feature = "keccak")]
+use crate::{Keccak256Builder, Keccak256Hasher};
+#[cfg(feature = "sha2")]
+use crate::{Sha256Builder, Sha256Hasher};
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Returns a hasher, to which a key can be fed in parts (e.g. chunks of a
+    /// network frame), without concatenating them into a single buffer.
+    ///
+    /// The key is made of the bytes of all the parts, so for streaming hash
+    /// functions (e.g. the default XXH3) the emitted sequence is the same as
+    /// with [`hash_iter_bytes`](Self::hash_iter_bytes) over their
+    /// concatenation, however the key is split. With
+    /// [`KeyHashing::Integer`](crate::KeyHashing::Integer), parts are folded
+    /// as words, so the sequence depends on how the key is split.
+    ///
+    /// ```
+    /// use hash_iter::DoubleHashHasher;
+    ///
+    /// let hasher = DoubleHashHasher::new();
+    /// let mut stream = hasher.stream_hasher();
+    /// stream.write(b"hello, ");
+    /// stream.write(b"world");
+    /// assert!(stream
+    ///     .finish_iter(3)
+    ///     .eq(hasher.hash_iter_bytes(b"hello, world", 3)));
+    /// ```
+    pub fn stream_hasher(&self) -> DoubleHashStreamHasher<'_, T, H1, H2> {
+        let mut state = match self.key_hashing {
+            KeyHashing::Double => State::Double(
+                self.hash_builder1.build_hasher(),
+                self.hash_builder2.build_hasher(),
+            ),
+            KeyHashing::Single128 { seed } => State::Single128(Box::new(Xxh3::with_seed(seed))),
+            KeyHashing::Mixed => State::Mixed(self.hash_builder1.build_hasher()),
+            KeyHashing::Integer { seed } => State::Integer {
+                seed: self.domain.map_or(seed, |tag| seed ^ tag),
+                hasher: IntegerHasher(0),
+            },
+            #[cfg(feature = "sha2")]
+            KeyHashing::Sha256 => State::Sha256(Box::new(Sha256Builder::new().build_hasher())),
+            #[cfg(feature = "keccak")]
+            KeyHashing::Keccak256 => {
+                State::Keccak256(Box::new(Keccak256Builder::new().build_hasher()))
+            }
+        };
+        // Same as hashing the `(tag, key)` pair (see `KeyWords`).
+        if let Some(tag) = self.domain {
+            state.write_tag(tag);
+        }
+        DoubleHashStreamHasher {
+            hasher: self,
+            state,
+        }
+    }
+}
+
+/// Hasher to which a key is fed in parts, see
+/// [`DoubleHashHasher::stream_hasher`].
+pub struct DoubleHashStreamHasher<'a, T, H1, H2>
+where
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    hasher: &'a DoubleHashHasher<T, H1, H2>,
+    state: State<H1::Hasher, H2::Hasher>,
+}
+
+impl<T, H1, H2> DoubleHashStreamHasher<'_, T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+    H1::Hasher: Clone,
+    H2::Hasher: Clone,
+{
+    /// Feeds the next part of the key.
+    pub fn write(&mut self, bytes: &[u8]) {
+        match &mut self.state {
+            State::Double(hasher1, hasher2) => {
+                hasher1.write(bytes);
+                hasher2.write(bytes);
+            }
+            State::Single128(hasher) => hasher.write(bytes),
+            State::Mixed(hasher) => hasher.write(bytes),
+            State::Integer { hasher, .. } => hasher.write(bytes),
+            #[cfg(feature = "sha2")]
+            State::Sha256(hasher) => hasher.write(bytes),
+            #[cfg(feature = "keccak")]
+            State::Keccak256(hasher) => hasher.write(bytes),
+        }
+    }
+
+    /// Returns an iterator over `count` hash values for the key fed so far.
+    pub fn finish_iter(self, count: usize) -> impl Iterator<Item = T> {
+        let hasher = self.hasher;
+        let mode = hasher.truncation_mode;
+        let x = T::Repr::from_hash_words(|i| mode.apply::<T::Repr>(self.state.word1(i)));
+        let y = T::Repr::from_hash_words(|i| mode.apply::<T::Repr>(self.state.word2(i)));
+        let n = hasher.n.to_repr();
+        if hasher.distinct {
+            HashIter::Distinct(Distinct::new(
+                hasher.sequence(x, y, n, u64::MAX),
+                count as u64,
+            ))
+        } else {
+            HashIter::Plain(hasher.sequence(x, y, n, count as u64))
+        }
+    }
+}
+
+/// Hashers the key is fed to, depending on the key hashing strategy.
+enum State<S1, S2> {
+    Double(S1, S2),
+    Single128(Box<Xxh3>),
+    Mixed(S1),
+    Integer {
+        seed: u64,
+        hasher: IntegerHasher,
+    },
+    #[cfg(feature = "sha2")]
+    Sha256(Box<Sha256Hasher>),
+    #[cfg(feature = "keccak")]
+    Keccak256(Box<Keccak256Hasher>),
+}
+
+impl<S1, S2> State<S1, S2>
+where
+    S1: Hasher,
+    S2: Hasher,
+{
+    /// Feeds the domain tag, ahead of the key.
+    fn write_tag(&mut self, tag: u64) {
+        match self {
+            Self::Double(hasher1, hasher2) => {
+                tag.hash(hasher1);
+                tag.hash(hasher2);
+            }
+            Self::Single128(hasher) => tag.hash(hasher),
+            Self::Mixed(hasher) => tag.hash(hasher),
+            #[cfg(feature = "sha2")]
+            Self::Sha256(hasher) => tag.hash(hasher),
+            #[cfg(feature = "keccak")]
+            Self::Keccak256(hasher) => tag.hash(hasher),
+            // Mixed into the seed instead.
+            Self::Integer { .. } => {}
+        }
+    }
+}
+
+impl<S1, S2> State<S1, S2>
+where
+    S1: Hasher + Clone,
+    S2: Hasher + Clone,
+{
+    /// Returns the `i`-th hash word of the first start point (see
+    /// `KeyWords::word1()`).
+    fn word1(&self, i: usize) -> u64 {
+        match self {
+            Self::Double(hasher, _) | Self::Mixed(hasher) => word(hasher, i),
+            Self::Single128(hasher) => word128(hasher, i) as u64,
+            Self::Integer { seed, hasher } => splitmix64(hasher.finish() ^ seed, 2 * i as u64),
+            #[cfg(feature = "sha2")]
+            Self::Sha256(hasher) => indexed(hasher, i).digest_words() as u64,
+            #[cfg(feature = "keccak")]
+            Self::Keccak256(hasher) => indexed(hasher, i).digest_words() as u64,
+        }
+    }
+
+    /// Returns the `i`-th hash word of the second start point (see
+    /// `KeyWords::word2()`).
+    fn word2(&self, i: usize) -> u64 {
+        match self {
+            Self::Double(_, hasher) => word(hasher, i),
+            Self::Single128(hasher) => (word128(hasher, i) >> 64) as u64,
+            Self::Mixed(hasher) => mix64(word(hasher, i)),
+            Self::Integer { seed, hasher } => splitmix64(hasher.finish() ^ seed, 2 * i as u64 + 1),
+            #[cfg(feature = "sha2")]
+            Self::Sha256(hasher) => (indexed(hasher, i).digest_words() >> 64) as u64,
+            #[cfg(feature = "keccak")]
+            Self::Keccak256(hasher) => (indexed(hasher, i).digest_words() >> 64) as u64,
+        }
+    }
+}
+
+/// Returns the `i`-th hash word of the key fed to `hasher`: subsequent words
+/// are obtained by hashing the word index after the key (see `hash_word()`).
+fn word<S: Hasher + Clone>(hasher: &S, i: usize) -> u64 {
+    if i == 0 {
+        return hasher.finish();
+    }
+    let mut hasher = hasher.clone();
+    i.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns a copy of `hasher`, to which the word index `i` is fed (if not the
+/// first one), see [`word`].
+#[cfg(any(feature = "keccak", feature = "sha2"))]
+fn indexed<S: Hasher + Clone>(hasher: &S, i: usize) -> S {
+    let mut hasher = hasher.clone();
+    if i > 0 {
+        i.hash(&mut hasher);
+    }
+    hasher
+}
+
+/// Same as [`word`], for 128-bit XXH3.
+fn word128(hasher: &Xxh3, i: usize) -> u128 {
+    if i == 0 {
+        return hasher.digest128();
+    }
+    let mut hasher = hasher.clone();
+    i.hash(&mut hasher);
+    hasher.digest128()
+}
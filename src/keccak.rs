@@ -0,0 +1,95 @@
+//! Keccak-256 hash builders, for agreeing with Ethereum-style tooling.
+
+use {
+    crate::{digest::digest_words, sealed, DigestHalf, DoubleHashHasher, KeyHashing},
+    sha3::{Digest, Keccak256},
+    std::hash,
+};
+
+/// Builder of [`Keccak256Hasher`]s.
+///
+/// Computes the original Keccak-256 (as used by Ethereum), which differs from
+/// the standardized SHA3-256 in its padding.
+///
+/// Available with the `keccak` feature. See
+/// [`DoubleHashHasher::with_keccak256`] for a ready-made double hasher.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Keccak256Builder {
+    half: DigestHalf,
+}
+
+impl Keccak256Builder {
+    /// Constructs a new builder, taking hash values out of the first half of
+    /// the digest.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the half of the digest the hash values are taken out of.
+    pub fn with_half(self, half: DigestHalf) -> Self {
+        Self { half }
+    }
+}
+
+impl hash::BuildHasher for Keccak256Builder {
+    type Hasher = Keccak256Hasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Keccak256Hasher {
+            keccak: Keccak256::new(),
+            half: self.half,
+        }
+    }
+}
+
+/// Keccak-256 hasher, see [`Keccak256Builder`].
+///
+/// The hash value is the leading 8 bytes of the configured half of the
+/// digest, read as a big-endian integer.
+#[derive(Debug, Clone)]
+pub struct Keccak256Hasher {
+    keccak: Keccak256,
+    half: DigestHalf,
+}
+
+impl hash::Hasher for Keccak256Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.keccak.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        self.half.word(&self.keccak.clone().finalize().into())
+    }
+}
+
+impl Keccak256Hasher {
+    /// Returns the words of both halves of the digest, see
+    /// [`KeyHashing::Keccak256`].
+    pub(crate) fn digest_words(&self) -> u128 {
+        digest_words(&self.keccak.clone().finalize().into())
+    }
+}
+
+impl<T> DoubleHashHasher<T, Keccak256Builder> {
+    /// Constructs a double hasher, whose starting hash points are taken out of
+    /// the two halves of the Keccak-256 digest of the key (computed once per
+    /// key, see [`KeyHashing::Keccak256`]).
+    ///
+    /// Wrap keys into [`RawBytes`](crate::RawBytes) to hash exactly their
+    /// bytes, as other Keccak-256 implementations do.
+    ///
+    /// ```
+    /// use hash_iter::{DoubleHashHasher, HashIterHasher, RawBytes};
+    ///
+    /// let hasher = DoubleHashHasher::with_keccak256(1000u64);
+    /// assert!(hasher.hash_iter(&RawBytes("hello"), 3).all(|h| h < 1000));
+    /// ```
+    pub fn with_keccak256(n: T) -> Self
+    where
+        T: sealed::Modulus,
+    {
+        let builder = Keccak256Builder::new();
+        Self::with_hash_builders(builder, builder.with_half(DigestHalf::Second), n)
+            .with_key_hashing(KeyHashing::Keccak256)
+    }
+}
@@ -1,141 +1,2274 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(feature = "nightly", feature(portable_simd, trusted_len, try_trait_v2))]
 
-use {num_traits, std::hash, xxhash_rust::xxh3::Xxh3Builder};
+#[cfg(feature = "ahash")]
+mod ahash;
+#[cfg(feature = "num-bigint")]
+mod bigint;
+#[cfg(feature = "fixed-bitset")]
+mod bitset;
+#[cfg(feature = "blake3")]
+mod blake3;
+mod blocked;
+mod caching;
+mod cuckoo;
+#[cfg(any(feature = "keccak", feature = "sha2"))]
+mod digest;
+mod distinct;
+mod family;
+mod feistel;
+mod fuse;
+#[cfg(feature = "hmac")]
+mod hmac;
+mod incremental;
+#[cfg(feature = "keccak")]
+mod keccak;
+mod multi;
+mod multiply_shift;
+#[cfg(feature = "murmur3")]
+mod murmur3;
+mod pairs;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod partitioned;
+mod portable;
+mod prefetch;
+mod probe;
+mod quadratic;
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "rapidhash")]
+mod rapidhash;
+#[cfg(feature = "io")]
+mod read;
+mod reduction;
+mod ribbon;
+#[cfg(feature = "sha2")]
+mod sha256;
+#[cfg(feature = "nightly")]
+mod simd;
+#[cfg(feature = "siphash")]
+mod siphash;
+mod strategy;
+#[cfg(feature = "futures")]
+mod stream;
+mod tabulation;
+mod wide;
+#[cfg(feature = "wyhash")]
+mod wyhash;
+
+#[cfg(feature = "num-bigint")]
+pub use bigint::BigHashes;
+#[cfg(feature = "blake3")]
+pub use blake3::{Blake3Builder, Blake3Hasher};
+#[cfg(any(feature = "keccak", feature = "sha2"))]
+pub use digest::DigestHalf;
+#[cfg(feature = "derive")]
+pub use hash_iter_derive::PortableKey;
+#[cfg(feature = "hmac")]
+pub use hmac::{HmacBuilder, HmacHasher};
+#[cfg(feature = "keccak")]
+pub use keccak::{Keccak256Builder, Keccak256Hasher};
+#[cfg(feature = "murmur3")]
+pub use murmur3::{Murmur3Builder, Murmur3Hasher, Murmur3Variant};
+#[cfg(feature = "rapidhash")]
+pub use rapidhash::RapidHashBuilder;
+#[cfg(feature = "sha2")]
+pub use sha256::{Sha256Builder, Sha256Hasher};
+#[cfg(feature = "siphash")]
+pub use siphash::{SipHashBuilder, SipHashHasher, SipRounds};
+#[cfg(feature = "wyhash")]
+pub use wyhash::WyHashBuilder;
+pub use {
+    caching::CachingHashIterHasher,
+    cuckoo::CuckooHash,
+    family::HashFamily,
+    feistel::FeistelPermutation,
+    incremental::DoubleHashStreamHasher,
+    multi::MultiHashes,
+    multiply_shift::{MultiplyShiftBuilder, MultiplyShiftHasher},
+    portable::{KeySink, PortableKey},
+    probe::ProbeSequence,
+    quadratic::QuadraticHashes,
+    reduction::{BitMask, Identity, Lemire, Modulo, RangeReduction, Reduction},
+    strategy::{ProbeStrategy, Probes},
+    tabulation::{TabulationBuilder, TabulationHasher},
+};
+use {
+    num_traits::{Bounded, FromPrimitive, One, ToPrimitive, Zero},
+    sealed::Sealed,
+    std::{array, cell::Cell, fmt, hash, hint, iter, ops},
+    xxhash_rust::xxh3::{self, Xxh3, Xxh3Builder},
+};
 
 /// Represents a number type.
 ///
 /// This allows to clients to create hashers that emit hashes of different sizes
-/// (`usize`, `u64`, and `u128` when `std::Hasher` supports emitting it).
+/// (`u8`, `u16`, `u32`, `u64`, `u128` and `usize`). With the `ruint` feature
+/// enabled, wide unsigned integers (e.g. `ruint::aliases::U256`) are supported
+/// as well.
+///
+/// Signed `i64` is supported as well, for interop with environments that lack
+/// unsigned integers (e.g. Java). Emitted values are always in `[0, n)`, so `n`
+/// must be positive; by default it is `i64::MAX`.
+///
+/// The trait is sealed, but can be used as a bound to write code that is
+/// generic over the output type:
+///
+/// ```
+/// use hash_iter::{HashIterHasher, Number};
+///
+/// fn first_hash<T: Number>(hasher: &impl HashIterHasher<T>, key: &str) -> Option<T> {
+///     hasher.hash_iter(&key, 1).next()
+/// }
+/// ```
 pub trait Number:
-    num_traits::Num
+    sealed::Sealed
+    + num_traits::Num
+    + num_traits::Bounded
     + num_traits::WrappingAdd
+    + num_traits::WrappingSub
     + num_traits::FromPrimitive
     + num_traits::ToPrimitive
+    + Ord
     + Copy
+    + fmt::Debug
 {
+    /// Fixed-width type the hash sequence is computed in.
+    ///
+    /// For unsigned fixed-width types this is the type itself. For `usize`,
+    /// computations are done in `u64`, so that the very same sequence is
+    /// produced on both 32-bit and 64-bit targets (for the same `n`). For
+    /// `i64`, computations are done in `u64` as well, so that the sequence
+    /// never goes negative.
+    type Repr: Number;
+
+    /// Converts a value from the representation type.
+    fn from_repr(v: Self::Repr) -> Self;
+
+    /// Converts a value into the representation type.
+    fn to_repr(self) -> Self::Repr;
+
+    /// Converts a 64-bit hash value into `Self`, keeping only the low bits
+    /// when `Self` is narrower than `u64`.
+    fn from_u64_truncated(v: u64) -> Self {
+        Self::from_u64(v).unwrap_or_else(|| {
+            let mask = Self::max_value().to_u64().expect("cannot create mask");
+            Self::from_u64(v & mask).expect("invalid hash point")
+        })
+    }
+
+    /// Builds a starting hash point out of 64-bit hash words.
+    ///
+    /// The `word(i)` closure returns the `i`-th 64-bit word of the key's hash.
+    /// Types up to 64 bits use only the first word, wider types may request as
+    /// many words as needed to fill all of their bits.
+    fn from_hash_words(mut word: impl FnMut(usize) -> u64) -> Self {
+        Self::from_u64_truncated(word(0))
+    }
+}
+
+mod sealed {
+    use {
+        super::{add_mod, Number},
+        num_traits::{Bounded, ToPrimitive},
+    };
+
+    /// Moduli for which the hasher can precompute a Barrett reciprocal.
+    pub trait Modulus {
+        /// Returns `floor((2^BITS - 1) / n)`, where `BITS` is the width of the
+        /// representation type, if the type supports fast multiply-high
+        /// (i.e. it is at most 128 bits wide).
+        fn reciprocal(&self) -> Option<u128>;
+    }
+
+    impl<T: Number> Modulus for T {
+        fn reciprocal(&self) -> Option<u128> {
+            let max = T::Repr::max_value().to_u128()?;
+            let n = self.to_repr().to_u128()?;
+            max.checked_div(n)
+        }
+    }
+
+    #[cfg(feature = "num-bigint")]
+    impl Modulus for num_bigint::BigUint {
+        fn reciprocal(&self) -> Option<u128> {
+            None
+        }
+    }
+
+    pub trait Sealed {
+        /// Computes `self * rhs (mod n)` without overflowing, for `self, rhs <
+        /// n`.
+        fn mul_mod(self, rhs: Self, n: Self) -> Self
+        where
+            Self: Number,
+        {
+            // Double-and-add, so that intermediate values never exceed `n`.
+            let two = Self::one() + Self::one();
+            let (mut acc, mut a, mut b) = (Self::zero(), self, rhs);
+            while !b.is_zero() {
+                if b % two == Self::one() {
+                    acc = add_mod(acc, a, n);
+                }
+                a = add_mod(a, a, n);
+                b = b / two;
+            }
+            acc
+        }
+
+        /// Computes the high half of the double-width product `self * rhs`,
+        /// i.e. `floor(self * rhs / 2^BITS)`.
+        fn mul_high(self, rhs: Self) -> Self
+        where
+            Self: Number,
+        {
+            // Shift-and-add, from the lowest bit of `rhs`: the bits shifted
+            // out of `acc` form the (discarded) low half of the product.
+            let two = Self::one() + Self::one();
+            let top = Self::max_value() / two + Self::one();
+            let (mut acc, mut b, mut bits) = (Self::zero(), rhs, Self::max_value());
+            while !bits.is_zero() {
+                let (sum, carry) = if b % two == Self::one() {
+                    let sum = acc.wrapping_add(&self);
+                    (sum, sum < acc)
+                } else {
+                    (acc, false)
+                };
+                acc = sum / two;
+                if carry {
+                    acc = acc + top;
+                }
+                b = b / two;
+                bits = bits / two;
+            }
+            acc
+        }
+
+        /// Computes `self mod n` for a power-of-two `n`, i.e. keeps the low
+        /// bits (without dividing, where the type supports bit operations).
+        fn low_bits(self, n: Self) -> Self
+        where
+            Self: Number,
+        {
+            self % n
+        }
+    }
+}
+
+/// Computes `a + b (mod n)` without overflowing, for `a, b < n` (`n = 0`
+/// stands for `2^BITS`, i.e. the addition simply wraps).
+#[inline]
+fn add_mod<R: Number>(a: R, b: R, n: R) -> R {
+    // Both candidates are computed unconditionally (one of them wraps), so
+    // that picking the right one compiles to a conditional move, rather than
+    // to a branch that mispredicts on random hash values.
+    let c = n.wrapping_sub(&b);
+    let (reduced, sum) = (a.wrapping_sub(&c), a.wrapping_add(&b));
+    hint::select_unpredictable(a >= c, reduced, sum)
+}
+
+/// Computes `a - b (mod n)` without overflowing, for `a, b < n` (`n = 0`
+/// stands for `2^BITS`).
+#[inline]
+fn sub_mod<R: Number>(a: R, b: R, n: R) -> R {
+    // `n - b` is only equal to `n` (out of range) for `b = 0`, which `add_mod`
+    // still handles correctly.
+    add_mod(a, n.wrapping_sub(&b), n)
+}
+
+/// Maps `y` into `[1, n)` (odd, if `n` is a power of two), for `n > 1`. For
+/// smaller `n` there is no non-zero step, so `y` is simply reduced.
+fn nonzero_step<R: Number>(y: R, n: R, reduction: &impl RangeReduction) -> R {
+    let one = R::one();
+    if n <= one {
+        return rem(y, n);
+    }
+
+    let y = one + reduction.reduce(y, n - one);
+    let even = (y % (one + one)).is_zero();
+    if even && is_power_of_two(n) {
+        // `y - 1` is odd and non-zero, since `y >= 2`.
+        y - one
+    } else {
+        y
+    }
+}
+
+fn is_power_of_two<R: Number>(n: R) -> bool {
+    match n.to_u128() {
+        Some(n) => n.is_power_of_two(),
+        None => {
+            let two = R::one() + R::one();
+            let mut n = n;
+            while (n % two).is_zero() {
+                n = n / two;
+            }
+            n == R::one()
+        }
+    }
+}
+
+/// Converts `v` into `R`, reduced modulo `n`.
+#[inline]
+fn from_u64_mod<R: Number>(v: u64, n: R) -> R {
+    match R::from_u64(v) {
+        Some(r) if r < n => r,
+        _ if n.is_zero() => R::from_u64_truncated(v),
+        Some(r) => r % n,
+        // `R` is narrower than `u64`, so `n` fits into `u64`.
+        None => R::from_u64(v % n.to_u64().expect("invalid n")).expect("invalid value"),
+    }
+}
+
+/// Same as [`from_u64_mod`], for `v <= 2^64` (e.g. `i + 1`, for any `u64`
+/// index `i`).
+#[inline]
+fn from_u128_mod<R: Number>(v: u128, n: R) -> R {
+    match u64::try_from(v) {
+        Ok(v) => from_u64_mod(v, n),
+        // `v = 2^64 = u64::MAX + 1`.
+        Err(_) => add_mod(from_u64_mod(u64::MAX, n), from_u64_mod(1, n), n),
+    }
+}
+
+/// Makes `y` odd (for `y < n` with `n` a power of two, `n > 1`, or with
+/// `n = 0`, the result is still in range).
+#[inline]
+fn odd<R: Number>(y: R) -> R {
+    let one = R::one();
+    if y.low_bits(one + one).is_zero() {
+        y + one
+    } else {
+        y
+    }
+}
+
+/// Computes `v mod n`, given the reciprocal `m = floor((2^BITS - 1) / n)`,
+/// using multiplications only (Barrett reduction).
+#[inline]
+fn barrett<R: Number>(v: R, n: R, m: R) -> R {
+    // The estimated quotient is either exact, or one less than the exact one.
+    let r = v - v.mul_high(m) * n;
+    if r >= n {
+        r - n
+    } else {
+        r
+    }
+}
+
+/// Computes `v mod n`, for `n > 0`, without branching on `v` (nor dividing it),
+/// for constant-time sequences.
+///
+/// Same as [`barrett`], with the final correction made by arithmetic, behind
+/// an optimization barrier, so that the compiler doesn't turn it back into a
+/// branch.
+#[inline]
+fn ct_rem<R: Number>(v: R, n: R) -> R {
+    // The reciprocal only depends on `n`, which is public.
+    let m = R::max_value() / n;
+    let r = v - v.mul_high(m) * n;
+    let over = hint::black_box(R::from_u8((r >= n) as u8).expect("invalid flag"));
+    r - over * n
+}
+
+/// Converts a seed of the output type into the 64-bit seed of a hash builder
+/// (negative `i64` seeds are taken as their two's complement).
+fn seed_to_u64<T: Number>(seed: T) -> u64 {
+    seed.to_u64()
+        .or_else(|| seed.to_i64().map(|seed| seed as u64))
+        .expect("seed must fit into 64 bits")
+}
+
+/// Converts a count of hash points of the output type into a 64-bit one,
+/// capping it at `u64::MAX`.
+fn count_to_u64<T: Number>(k: T) -> u64 {
+    assert!(k >= T::zero(), "k must not be negative");
+    k.to_u64().unwrap_or(u64::MAX)
+}
+
+/// Returns the number of slots of a table of size `n` (`n = 0` stands for
+/// `2^BITS`), saturating at `u64::MAX`.
+fn slot_count<R: Number>(n: R) -> u64 {
+    if n.is_zero() {
+        R::max_value()
+            .to_u64()
+            .map_or(u64::MAX, |max| max.saturating_add(1))
+    } else {
+        n.to_u64().unwrap_or(u64::MAX)
+    }
+}
+
+/// Reduces `v` modulo `n`, skipping the division if `v` is already in range.
+#[inline]
+fn rem<R: Number>(v: R, n: R) -> R {
+    if v < n || n.is_zero() {
+        v
+    } else {
+        v % n
+    }
+}
+
+macro_rules! impl_number {
+    ($($t:ty),*) => {
+        $(
+            impl sealed::Sealed for $t {
+                #[inline]
+                fn mul_mod(self, rhs: Self, n: Self) -> Self {
+                    if n == 0 {
+                        return self.wrapping_mul(rhs);
+                    }
+                    ((self as u128 * rhs as u128) % n as u128) as Self
+                }
+
+                #[inline]
+                fn mul_high(self, rhs: Self) -> Self {
+                    ((self as u128 * rhs as u128) >> Self::BITS) as Self
+                }
+
+                #[inline]
+                fn low_bits(self, n: Self) -> Self {
+                    self & (n - 1)
+                }
+            }
+
+            impl Number for $t {
+                type Repr = Self;
+
+                #[inline]
+                fn from_repr(v: Self::Repr) -> Self {
+                    v
+                }
+
+                #[inline]
+                fn to_repr(self) -> Self::Repr {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+impl_number!(u8, u16, u32, u64);
+
+impl sealed::Sealed for u128 {
+    #[inline]
+    fn mul_high(self, rhs: Self) -> Self {
+        const LOW: u128 = u64::MAX as u128;
+        let (a1, a0) = (self >> 64, self & LOW);
+        let (b1, b0) = (rhs >> 64, rhs & LOW);
+        let (mid1, mid2) = (a1 * b0, a0 * b1);
+        let carry = (((a0 * b0) >> 64) + (mid1 & LOW) + (mid2 & LOW)) >> 64;
+        a1 * b1 + (mid1 >> 64) + (mid2 >> 64) + carry
+    }
+
+    #[inline]
+    fn low_bits(self, n: Self) -> Self {
+        self & (n - 1)
+    }
+}
+
+impl Number for u128 {
+    type Repr = Self;
+
+    #[inline]
+    fn from_repr(v: Self::Repr) -> Self {
+        v
+    }
+
+    #[inline]
+    fn to_repr(self) -> Self::Repr {
+        self
+    }
+
+    /// Uses two 64-bit hash words, so that the full 128-bit range is covered.
+    fn from_hash_words(mut word: impl FnMut(usize) -> u64) -> Self {
+        (word(0) as u128) | ((word(1) as u128) << 64)
+    }
+}
+
+#[cfg(feature = "ruint")]
+impl<const BITS: usize, const LIMBS: usize> sealed::Sealed for ruint::Uint<BITS, LIMBS> {
+    #[inline]
+    fn mul_mod(self, rhs: Self, n: Self) -> Self {
+        if n.is_zero() {
+            return self.wrapping_mul(rhs);
+        }
+        ruint::Uint::mul_mod(self, rhs, n)
+    }
+
+    #[inline]
+    fn low_bits(self, n: Self) -> Self {
+        self & (n - Self::from(1))
+    }
+}
+
+/// Arbitrary fixed-width unsigned integers (e.g. `U256`), available with the
+/// `ruint` feature.
+///
+/// Starting hash points are filled with as many 64-bit hash words as there are
+/// limbs, so that the whole `[0, n)` range is covered even for very wide types.
+#[cfg(feature = "ruint")]
+impl<const BITS: usize, const LIMBS: usize> Number for ruint::Uint<BITS, LIMBS> {
+    type Repr = Self;
+
+    #[inline]
+    fn from_repr(v: Self::Repr) -> Self {
+        v
+    }
+
+    #[inline]
+    fn to_repr(self) -> Self::Repr {
+        self
+    }
+
+    fn from_hash_words(mut word: impl FnMut(usize) -> u64) -> Self {
+        let mut limbs = [0; LIMBS];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            *limb = word(i);
+        }
+        if let Some(last) = limbs.last_mut() {
+            *last &= Self::MASK;
+        }
+        Self::from_limbs(limbs)
+    }
+}
+
+impl sealed::Sealed for i64 {}
+
+impl Number for i64 {
+    type Repr = u64;
+
+    #[inline]
+    fn from_repr(v: Self::Repr) -> Self {
+        // Values are always reduced modulo positive `n`, so they fit in `i64`.
+        v as i64
+    }
+
+    #[inline]
+    fn to_repr(self) -> Self::Repr {
+        self as u64
+    }
+}
+
+impl sealed::Sealed for usize {}
+
+impl Number for usize {
+    type Repr = u64;
+
+    #[inline]
+    fn from_repr(v: Self::Repr) -> Self {
+        // Values are always reduced modulo `n`, which itself fits in `usize`.
+        v as usize
+    }
+
+    #[inline]
+    fn to_repr(self) -> Self::Repr {
+        self as u64
+    }
+}
+
+/// Provides an iterator over multiple hash values for a given key.
+pub trait HashIterHasher<T> {
+    /// Returns an iterator over `count` number of hash values generated using
+    /// enhanced double hashing.
+    fn hash_iter<K: hash::Hash + ?Sized>(&self, key: &K, count: usize) -> impl Iterator<Item = T>;
+
+    /// Same as [`hash_iter`](Self::hash_iter), but takes a 64-bit `count`, so
+    /// that the same (long) sequences can be requested on all targets,
+    /// regardless of pointer width.
+    ///
+    /// The default implementation panics if `count` does not fit into `usize`.
+    fn hash_iter_u64<K: hash::Hash + ?Sized>(
+        &self,
+        key: &K,
+        count: u64,
+    ) -> impl Iterator<Item = T> {
+        let count = usize::try_from(count).expect("count does not fit into usize");
+        self.hash_iter(key, count)
+    }
+
+    /// Writes hash values for the given key directly into `buf`, one per slot,
+    /// without allocating.
+    ///
+    /// Returns the number of hash values written.
+    fn hash_iter_into<K: hash::Hash + ?Sized>(&self, key: &K, buf: &mut [T]) -> usize {
+        let hashes = self.hash_iter(key, buf.len());
+        buf.iter_mut()
+            .zip(hashes)
+            .map(|(slot, hash)| *slot = hash)
+            .count()
+    }
+
+    /// Writes `count` hash values for each of the `keys` into `out`, which is
+    /// treated as a row-major matrix: hash values of `keys[i]` are written to
+    /// `out[i * count..(i + 1) * count]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out.len()` is not equal to `keys.len() * count`.
+    fn hash_iter_batch<K: hash::Hash>(&self, keys: &[K], count: usize, out: &mut [T]) {
+        let len = keys.len().checked_mul(count);
+        assert_eq!(len, Some(out.len()), "out must hold count hashes per key");
+        if count == 0 {
+            return;
+        }
+        for (key, row) in keys.iter().zip(out.chunks_exact_mut(count)) {
+            self.hash_iter_into(key, row);
+        }
+    }
+
+    /// Returns a fixed-size array of `N` hash values for the given key.
+    ///
+    /// Useful when the number of hashes is known at compile time (e.g. `k = 7`
+    /// for a Bloom filter), as results are stack-allocated.
+    fn hash_array<const N: usize, K: hash::Hash + ?Sized>(&self, key: &K) -> [T; N] {
+        let mut hashes = self.hash_iter(key, N);
+        array::from_fn(|_| hashes.next().expect("not enough hash values"))
+    }
+
+    /// Returns an iterator over `count` chunks of `M` hash values each, i.e.
+    /// the first `count * M` hash values of the given key, grouped into
+    /// arrays.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count * M` overflows `usize`.
+    fn hash_iter_chunks<const M: usize, K: hash::Hash + ?Sized>(
+        &self,
+        key: &K,
+        count: usize,
+    ) -> impl Iterator<Item = [T; M]> {
+        let total = count.checked_mul(M).expect("too many hash values");
+        let mut hashes = self.hash_iter(key, total);
+        (0..count).map(move |_| array::from_fn(|_| hashes.next().expect("not enough hash values")))
+    }
+
+    /// Invokes `f` for each of the `count` hash values of the given key,
+    /// instead of materializing an iterator.
+    fn hash_for_each<K, F>(&self, key: &K, count: usize, f: F)
+    where
+        K: hash::Hash + ?Sized,
+        F: FnMut(T),
+    {
+        self.hash_iter(key, count).for_each(f)
+    }
+
+    /// Returns an iterator over `count` pairs of `(i, hash)`, where `i` is the
+    /// probe number of the hash value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` does not fit into `u32`.
+    fn hash_iter_enumerated<K: hash::Hash + ?Sized>(
+        &self,
+        key: &K,
+        count: usize,
+    ) -> impl Iterator<Item = (u32, T)> {
+        let probes = u32::try_from(count).expect("count does not fit into u32");
+        (0..probes).zip(self.hash_iter(key, count))
+    }
+
+    /// Collects `count` hash values of the given key into a `SmallVec`, which
+    /// only allocates on the heap if `count` exceeds `N`.
+    ///
+    /// Available with the `smallvec` feature.
+    #[cfg(feature = "smallvec")]
+    fn hash_iter_smallvec<const N: usize, K: hash::Hash + ?Sized>(
+        &self,
+        key: &K,
+        count: usize,
+    ) -> smallvec::SmallVec<[T; N]> {
+        self.hash_iter(key, count).collect()
+    }
+}
+
+/// Builds hash iterator hasher -- a hasher capable of generating multiple hash
+/// values.
+pub trait BuildHashIterHasher<T> {
+    type Hasher: HashIterHasher<T>;
+
+    fn build_hash_iter_hasher(&self) -> Self::Hasher;
+}
+
+/// Hash builder that can be seeded with a 64-bit value.
+///
+/// Allows [`DoubleHashBuilder`] to derive both starting points from the same
+/// type of hash builder, seeded differently (see
+/// [`DoubleHashBuilder::new_with`]).
+pub trait SeedableBuildHasher: hash::BuildHasher + Default {
+    /// Returns the hash builder with the given seed.
+    fn with_seed(self, seed: u64) -> Self;
+
+    /// Returns the hash builder with the given seed, keyed with the secret
+    /// set by [`DoubleHashBuilder::with_secret`], if any.
+    ///
+    /// The default implementation ignores the secret.
+    fn with_seed_and_secret(self, seed: u64, secret: Option<&[u8; 192]>) -> Self {
+        let _ = secret;
+        self.with_seed(seed)
+    }
+}
+
+impl SeedableBuildHasher for Xxh3Builder {
+    fn with_seed(self, seed: u64) -> Self {
+        // The custom secret, if any, is kept.
+        Xxh3Builder::with_seed(self, seed)
+    }
+
+    fn with_seed_and_secret(self, seed: u64, secret: Option<&[u8; 192]>) -> Self {
+        match secret {
+            // Seeded XXH3 ignores the secret for keys of at most 240 bytes, so
+            // the seed goes into the secret instead, and XXH3 runs unseeded.
+            Some(secret) => Xxh3Builder::with_seed(self, 0).with_secret(xxh3_secret(secret, seed)),
+            None => Xxh3Builder::with_seed(self, seed),
+        }
+    }
+}
+
+/// Derives the XXH3 secret of a start point from the shared secret and the
+/// seed of the start point.
+fn xxh3_secret(secret: &[u8; 192], seed: u64) -> [u8; 192] {
+    let mut derived = [0; 192];
+    for (i, word) in derived.chunks_exact_mut(8).enumerate() {
+        let h = xxh3::xxh3_64_with_seed(secret, splitmix64(seed, i as u64));
+        word.copy_from_slice(&h.to_le_bytes());
+    }
+    derived
+}
+
+/// Strategy for converting 64-bit hash values into narrower output types.
+///
+/// Only affects output types narrower than 64 bits (e.g. `u32` or `u16`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TruncationMode {
+    /// Keep only the low bits of the hash value.
+    #[default]
+    Low,
+
+    /// XOR-fold all the bits of the hash value into the width of the output
+    /// type, so that entropy from the high bits is preserved.
+    XorFold,
+}
+
+impl TruncationMode {
+    /// Prepares a 64-bit hash value for conversion into `T`.
+    fn apply<T: Number>(self, v: u64) -> u64 {
+        match (self, T::max_value().to_u64()) {
+            (Self::XorFold, Some(mask)) if mask != u64::MAX => {
+                let bits = mask.count_ones();
+                let (mut v, mut folded) = (v, 0);
+                // A fixed number of rounds, so that the time taken doesn't
+                // depend on the hash value.
+                for _ in 0..u64::BITS.div_ceil(bits) {
+                    folded ^= v & mask;
+                    v >>= bits;
+                }
+                folded
+            }
+            _ => v,
+        }
+    }
+}
+
+/// Recurrence used to generate the sequence of hash points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HashingMode {
+    /// Enhanced double hashing: `h(i) = h1 + i * h2 + (i^3-i)/6 (mod n)`.
+    #[default]
+    Enhanced,
+
+    /// Plain (Kirsch-Mitzenmacher) double hashing: `h(i) = h1 + i * h2 (mod
+    /// n)`, i.e. without the cubic term.
+    ///
+    /// Useful for compatibility with existing filters built using the classic
+    /// scheme. Note that if `h2` is `0 (mod n)`, all the hash points are the
+    /// same.
+    Plain,
+
+    /// Linear probing: `h(i) = h1 + i * stride (mod n)`, i.e. the second hash
+    /// is replaced by a fixed stride.
+    Linear {
+        /// The distance between consecutive hash points.
+        stride: u64,
+    },
+}
+
+/// Strategy for deriving the two start points of the sequence from the key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum KeyHashing {
+    /// Hash the key twice, once with each hash builder.
+    #[default]
+    Double,
+
+    /// Hash the key once, with the given seed, using 128-bit XXH3: the low and
+    /// high halves of the hash become the first and the second start points.
+    ///
+    /// The hash builders are not used. For output types wider than 64 bits,
+    /// the key is hashed once more per extra 64-bit word.
+    Single128 {
+        /// The seed of the XXH3 hash.
+        seed: u64,
+    },
+
+    /// Hash the key once, with the first hash builder, and derive the second
+    /// start point by mixing the first one (using the SplitMix64 finalizer).
+    ///
+    /// The second hash builder is not used.
+    Mixed,
+
+    /// Skip hashing bytes for integer keys: the value of the key is captured
+    /// directly, and both start points are derived from it (and the seed)
+    /// using the SplitMix64 generator.
+    ///
+    /// Meant for keys hashing as a single integer (e.g. `u64` IDs, or
+    /// newtypes over them), for which distinct keys are guaranteed to have
+    /// distinct first start points (before the reduction). Other keys are
+    /// folded into 64 bits, which is fast but weak, so they are better hashed
+    /// with the other strategies. The hash builders are not used.
+    Integer {
+        /// The seed, mixed with the value of the key.
+        seed: u64,
+    },
+
+    /// Hash the key once with SHA-256: the leading 8 bytes of the first and
+    /// the second halves of the digest (read as big-endian integers) become
+    /// the first and the second start points.
+    ///
+    /// The hash builders are not used. For output types wider than 64 bits,
+    /// the key is hashed once more per extra 64-bit word. Available with the
+    /// `sha2` feature.
+    #[cfg(feature = "sha2")]
+    Sha256,
+
+    /// Same as [`Sha256`](Self::Sha256), with Keccak-256 (as used by
+    /// Ethereum). Available with the `keccak` feature.
+    #[cfg(feature = "keccak")]
+    Keccak256,
+}
+
+/// Holds the state for the hasher that implements enhanced double hashing.
+///
+/// Serves as a builder, allowing to configure the hasher with custom seeds,
+/// number of required hashes, and the size of the hash table.
+///
+/// The start points are computed with XXH3 by default, but any hash builders
+/// can be used instead (see [`with_hash_builder1`](Self::with_hash_builder1)
+/// and [`with_hash_builder2`](Self::with_hash_builder2)).
+#[derive(Clone, Copy)]
+pub struct DoubleHashBuilder<T: Number = u64, H1 = Xxh3Builder, H2 = Xxh3Builder> {
+    hash_builder1: H1,
+    hash_builder2: H2,
+    /// The seeds last set, kept to key the hash builders with the secret.
+    seeds: [u64; 2],
+    secret: Option<[u8; 192]>,
+    n: T,
+    truncation_mode: TruncationMode,
+    hashing_mode: HashingMode,
+    key_hashing: KeyHashing,
+    reduction: Reduction,
+    nonzero_h2: bool,
+    distinct: bool,
+    domain: Option<u64>,
+    constant_time: bool,
+}
+
+impl<T: Number> DoubleHashBuilder<T> {
+    /// Constructs a new hash iterator builder, with default seeds.
+    pub fn new() -> Self {
+        Self::new_with()
+    }
+
+    /// Constructs a new hash iterator builder, computing the starting points
+    /// with hash builders of type `H` (instead of XXH3), with default seeds.
+    ///
+    /// ```
+    /// use hash_iter::{BuildHashIterHasher, DoubleHashBuilder, HashIterHasher, MultiplyShiftBuilder};
+    ///
+    /// let hasher = DoubleHashBuilder::<u64>::new_with::<MultiplyShiftBuilder>()
+    ///     .with_seed1(1)
+    ///     .with_seed2(2)
+    ///     .with_n(1000)
+    ///     .build_hash_iter_hasher();
+    /// assert!(hasher.hash_iter(&"hello", 3).all(|h| h < 1000));
+    /// ```
+    pub fn new_with<H: SeedableBuildHasher>() -> DoubleHashBuilder<T, H, H> {
+        // Seeds for double hashing: essentially, we can use any seeds, to
+        // initialize the hasher (by default XXH3 uses `0`).
+        let seed1 = 12345;
+        let seed2 = 67890;
+        // By default, `n` is `usize::MAX`, capped at the maximum value of `T`
+        // for types narrower than `usize`.
+        let n = T::from_u64(usize::MAX as u64).unwrap_or_else(T::max_value);
+        DoubleHashBuilder {
+            hash_builder1: H::default().with_seed(seed1),
+            hash_builder2: H::default().with_seed(seed2),
+            seeds: [seed1, seed2],
+            secret: None,
+            n,
+            truncation_mode: TruncationMode::default(),
+            hashing_mode: HashingMode::default(),
+            key_hashing: KeyHashing::default(),
+            reduction: Reduction::default(),
+            nonzero_h2: false,
+            distinct: false,
+            domain: None,
+            constant_time: false,
+        }
+    }
+
+    /// Constructs a new hash iterator builder, with default seeds, and with
+    /// the guards against degenerate sequences enabled (see
+    /// [`with_nonzero_h2`](Self::with_nonzero_h2)).
+    pub fn safe() -> Self {
+        Self::new().with_nonzero_h2(true)
+    }
+
+    /// Sets a custom XXH3 secret, shared by both start points.
+    ///
+    /// The secret affects keys of any length: each start point is hashed with
+    /// unseeded XXH3, using its own secret derived from the given one and the
+    /// seed of the start point (so that the seeds still tell them apart). Note
+    /// that hash builders set with
+    /// [`with_hash_builder1`](Self::with_hash_builder1)
+    /// (or [`with_hash_builder2`](Self::with_hash_builder2)) are re-seeded with
+    /// the seeds last set on this builder.
+    ///
+    /// The secret is expected to look random, e.g. be drawn from a CSPRNG:
+    /// structured secrets weaken the hash function.
+    pub fn with_secret(self, secret: &[u8; 192]) -> Self {
+        let [seed1, seed2] = self.seeds;
+        Self {
+            hash_builder1: self.hash_builder1.with_seed_and_secret(seed1, Some(secret)),
+            hash_builder2: self.hash_builder2.with_seed_and_secret(seed2, Some(secret)),
+            secret: Some(*secret),
+            ..self
+        }
+    }
+}
+
+impl<T: Number, H1: SeedableBuildHasher, H2> DoubleHashBuilder<T, H1, H2> {
+    /// Sets the seed of the first hash builder.
+    ///
+    /// The seeds should differ: with identical hash builders both start points
+    /// are equal (see
+    /// [`try_build_hash_iter_hasher`](Self::try_build_hash_iter_hasher)).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the seed does not fit into 64 bits.
+    pub fn with_seed1(self, seed1: T) -> Self {
+        let seed1 = seed_to_u64(seed1);
+        Self {
+            hash_builder1: self
+                .hash_builder1
+                .with_seed_and_secret(seed1, self.secret.as_ref()),
+            seeds: [seed1, self.seeds[1]],
+            ..self
+        }
+    }
+}
+
+impl<T: Number, H1, H2: SeedableBuildHasher> DoubleHashBuilder<T, H1, H2> {
+    /// Sets the seed of the second hash builder (see
+    /// [`with_seed1`](Self::with_seed1)).
+    pub fn with_seed2(self, seed2: T) -> Self {
+        let seed2 = seed_to_u64(seed2);
+        Self {
+            hash_builder2: self
+                .hash_builder2
+                .with_seed_and_secret(seed2, self.secret.as_ref()),
+            seeds: [self.seeds[0], seed2],
+            ..self
+        }
+    }
 }
 
-impl<T> Number for T where
-    T: num_traits::Num
-        + num_traits::WrappingAdd
-        + num_traits::FromPrimitive
-        + num_traits::ToPrimitive
-        + Copy
-{
-}
+impl<T, H1, H2> DoubleHashBuilder<T, H1, H2>
+where
+    T: Number,
+    H1: SeedableBuildHasher,
+    H2: SeedableBuildHasher,
+{
+    /// Seeds both hash builders with two independent seeds, derived from a
+    /// single master seed using the SplitMix64 generator.
+    ///
+    /// Safer than hand-picked pairs of seeds, which tend to be correlated, and
+    /// easier to store.
+    pub fn with_seed(self, master: u64) -> Self {
+        self.with_u64_seeds(splitmix64(master, 0), splitmix64(master, 1))
+    }
+
+    /// Seeds both hash builders with 64-bit seeds, whatever the output type.
+    pub(crate) fn with_u64_seeds(self, seed1: u64, seed2: u64) -> Self {
+        let secret = self.secret.as_ref();
+        Self {
+            hash_builder1: self.hash_builder1.with_seed_and_secret(seed1, secret),
+            hash_builder2: self.hash_builder2.with_seed_and_secret(seed2, secret),
+            seeds: [seed1, seed2],
+            ..self
+        }
+    }
+
+    /// Seeds both hash builders with a master seed derived from the given
+    /// bytes (their XXH3 hash), see [`with_seed`](Self::with_seed).
+    pub fn with_seed_bytes(self, bytes: &[u8]) -> Self {
+        self.with_seed(xxh3::xxh3_64(bytes))
+    }
+
+    /// Seeds both hash builders with a master seed derived from the given
+    /// string, e.g. a human-readable seed from a configuration file (same as
+    /// [`with_seed_bytes`](Self::with_seed_bytes) for its UTF-8 bytes).
+    pub fn with_seed_str(self, seed: &str) -> Self {
+        self.with_seed_bytes(seed.as_bytes())
+    }
+}
+
+impl<T: Number, H1, H2> DoubleHashBuilder<T, H1, H2> {
+    /// Sets the hash builder computing the first start point (replacing the
+    /// seeded XXH3 one), e.g. to mix hash functions.
+    pub fn with_hash_builder1<H>(self, hash_builder1: H) -> DoubleHashBuilder<T, H, H2> {
+        DoubleHashBuilder {
+            hash_builder1,
+            hash_builder2: self.hash_builder2,
+            seeds: self.seeds,
+            secret: self.secret,
+            n: self.n,
+            truncation_mode: self.truncation_mode,
+            hashing_mode: self.hashing_mode,
+            key_hashing: self.key_hashing,
+            reduction: self.reduction,
+            nonzero_h2: self.nonzero_h2,
+            distinct: self.distinct,
+            domain: self.domain,
+            constant_time: self.constant_time,
+        }
+    }
+
+    /// Sets the hash builder computing the second start point (replacing the
+    /// seeded XXH3 one).
+    pub fn with_hash_builder2<H>(self, hash_builder2: H) -> DoubleHashBuilder<T, H1, H> {
+        DoubleHashBuilder {
+            hash_builder1: self.hash_builder1,
+            hash_builder2,
+            seeds: self.seeds,
+            secret: self.secret,
+            n: self.n,
+            truncation_mode: self.truncation_mode,
+            hashing_mode: self.hashing_mode,
+            key_hashing: self.key_hashing,
+            reduction: self.reduction,
+            nonzero_h2: self.nonzero_h2,
+            distinct: self.distinct,
+            domain: self.domain,
+            constant_time: self.constant_time,
+        }
+    }
+
+    /// Sets the size of the hash table, i.e. hash values are in `[0, n)`.
+    ///
+    /// `n = 0` stands for the whole range of the output type, see
+    /// [`with_full_range`](Self::with_full_range).
+    pub fn with_n(self, n: T) -> Self {
+        Self { n, ..self }
+    }
+
+    /// Makes hash values cover the whole range of the output type, i.e. the
+    /// sequence is computed modulo `2^BITS`, using plain wrapping arithmetic
+    /// (no reductions at all). Same as `with_n(0)`.
+    ///
+    /// Note that this is a different sequence than the one for `n = T::MAX`
+    /// (i.e. modulo `2^BITS - 1`). For `i64`, the values are computed modulo
+    /// `2^64`, so they may be negative.
+    pub fn with_full_range(self) -> Self {
+        self.with_n(T::zero())
+    }
+
+    /// Sets the strategy for converting 64-bit hashes into narrower types.
+    pub fn with_truncation_mode(self, truncation_mode: TruncationMode) -> Self {
+        Self {
+            truncation_mode,
+            ..self
+        }
+    }
+
+    /// Sets the recurrence used to generate hash points.
+    pub fn with_hashing_mode(self, hashing_mode: HashingMode) -> Self {
+        Self {
+            hashing_mode,
+            ..self
+        }
+    }
+
+    /// Sets the strategy for deriving the start points from the key.
+    pub fn with_key_hashing(self, key_hashing: KeyHashing) -> Self {
+        Self {
+            key_hashing,
+            ..self
+        }
+    }
+
+    /// Sets the method of mapping the start points into `[0, n)`, e.g.
+    /// `with_reduction(Lemire)` (see [`RangeReduction`]).
+    ///
+    /// [`Reduction::BitMask`] requires `n` to be a power of two (checked when
+    /// the hasher is built), while [`Reduction::Auto`] picks it whenever `n`
+    /// allows, and [`Reduction::Modulo`] otherwise.
+    pub fn with_reduction(self, reduction: impl Into<Reduction>) -> Self {
+        Self {
+            reduction: reduction.into(),
+            ..self
+        }
+    }
+
+    /// Forces the second start point into `[1, n)` (and to be odd, when `n`
+    /// is a power of two).
+    ///
+    /// If `h2` is `0 (mod n)`, the plain double hashing sequence is stuck on
+    /// a single slot (and the enhanced one covers much fewer slots than it
+    /// could). With an odd `h2` and power-of-two `n`, the plain sequence visits
+    /// all the slots. Disabled by default, so that existing sequences are not
+    /// changed.
+    pub fn with_nonzero_h2(self, nonzero_h2: bool) -> Self {
+        Self { nonzero_h2, ..self }
+    }
+
+    /// Guarantees that the first `min(k, n)` emitted hash values are pairwise
+    /// distinct (see
+    /// [`DoubleHashHasher::hash_iter_distinct`](DoubleHashHasher::hash_iter_distinct)
+    /// for how duplicates are skipped).
+    ///
+    /// Affects the [`HashIterHasher`] methods. Tracking the emitted values has
+    /// a cost, so this is only worth it for small tables, where duplicates are
+    /// frequent.
+    pub fn with_distinct(self, distinct: bool) -> Self {
+        Self { distinct, ..self }
+    }
+
+    /// Sets the domain (namespace) tag, e.g. `"user-ids"`, folded into the
+    /// hashing of every key, so that hashers of different domains never share
+    /// sequences, even with identical seeds (see
+    /// [`DoubleHashHasher::with_domain`]).
+    pub fn with_domain(self, domain: &str) -> Self {
+        Self {
+            domain: Some(domain_tag(domain)),
+            ..self
+        }
+    }
+
+    /// Generates the sequences without branching on secret values, so that
+    /// timing doesn't leak information about the keys (see
+    /// [`DoubleHashHasher::with_constant_time`]).
+    ///
+    /// Building the hasher panics if [`with_distinct`](Self::with_distinct)
+    /// is enabled too.
+    pub fn with_constant_time(self, constant_time: bool) -> Self {
+        Self {
+            constant_time,
+            ..self
+        }
+    }
+}
+
+impl<T: Number> Default for DoubleHashBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, H1, H2> BuildHashIterHasher<T> for DoubleHashBuilder<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher + Clone,
+    H2: hash::BuildHasher + Clone,
+{
+    type Hasher = DoubleHashHasher<T, H1, H2>;
+
+    /// Builds the hasher.
+    ///
+    /// Identical hash builders are accepted, see
+    /// [`try_build_hash_iter_hasher`](DoubleHashBuilder::try_build_hash_iter_hasher)
+    /// to reject them.
+    ///
+    /// # Panics
+    ///
+    /// Panics with [`Reduction::BitMask`] for `n` not a power of two.
+    fn build_hash_iter_hasher(&self) -> Self::Hasher {
+        match self.resolve_reduction() {
+            Ok(reduction) => self.build(reduction),
+            Err(err) => panic!("{err}"),
+        }
+    }
+}
+
+impl<T, H1, H2> DoubleHashBuilder<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher + Clone,
+    H2: hash::BuildHasher + Clone,
+{
+    /// Builds the hasher, checking the configuration for consistency.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError::IdenticalHashBuilders`] if, with
+    /// [`KeyHashing::Double`], both hash builders produce the same hashes
+    /// (e.g. XXH3 with identical seeds), which would make `h1` always equal to
+    /// `h2`, and [`BuildError::BitMaskNotPowerOfTwo`] for
+    /// [`Reduction::BitMask`] with `n` not a power of two.
+    ///
+    /// ```
+    /// use hash_iter::{BuildError, DoubleHashBuilder};
+    ///
+    /// let builder = DoubleHashBuilder::<u64>::new().with_seed1(7).with_seed2(7);
+    /// assert_eq!(
+    ///     builder.try_build_hash_iter_hasher().err(),
+    ///     Some(BuildError::IdenticalHashBuilders)
+    /// );
+    /// ```
+    pub fn try_build_hash_iter_hasher(&self) -> Result<DoubleHashHasher<T, H1, H2>, BuildError> {
+        if self.key_hashing == KeyHashing::Double {
+            // Identical hash builders agree on any key, so a single probe is
+            // enough (distinct ones collide with probability 2^-64).
+            let probe = 0x5eed_u64;
+            if self.hash_builder1.hash_one(probe) == self.hash_builder2.hash_one(probe) {
+                return Err(BuildError::IdenticalHashBuilders);
+            }
+        }
+        Ok(self.build(self.resolve_reduction()?))
+    }
+
+    /// Resolves the configured reduction for `n`.
+    fn resolve_reduction(&self) -> Result<Reduction, BuildError> {
+        let pow2 = is_power_of_two(self.n.to_repr());
+        Ok(match self.reduction {
+            Reduction::Auto if pow2 => Reduction::BitMask,
+            Reduction::Auto => Reduction::Modulo,
+            Reduction::BitMask if !pow2 && !self.n.is_zero() => {
+                return Err(BuildError::BitMaskNotPowerOfTwo);
+            }
+            reduction => reduction,
+        })
+    }
+
+    /// Builds the hasher with the (resolved) reduction.
+    fn build(&self, reduction: Reduction) -> DoubleHashHasher<T, H1, H2> {
+        let hasher = DoubleHashHasher::with_hash_builders(
+            self.hash_builder1.clone(),
+            self.hash_builder2.clone(),
+            self.n,
+        )
+        .with_truncation_mode(self.truncation_mode)
+        .with_hashing_mode(self.hashing_mode)
+        .with_key_hashing(self.key_hashing)
+        .with_reduction(reduction)
+        .with_nonzero_h2(self.nonzero_h2)
+        .with_distinct(self.distinct)
+        .with_constant_time(self.constant_time);
+        DoubleHashHasher {
+            domain: self.domain,
+            ..hasher
+        }
+    }
+}
+
+/// Inconsistent configuration of a [`DoubleHashBuilder`], see
+/// [`try_build_hash_iter_hasher`](DoubleHashBuilder::try_build_hash_iter_hasher).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// Both hash builders produce the same hashes (e.g. XXH3 with identical
+    /// seeds), so `h1` always equals `h2`.
+    IdenticalHashBuilders,
+
+    /// [`Reduction::BitMask`] is set, but `n` is not a power of two.
+    BitMaskNotPowerOfTwo,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::IdenticalHashBuilders => {
+                "hash builders must differ (e.g. seed1 != seed2), or h1 always equals h2"
+            }
+            Self::BitMaskNotPowerOfTwo => "n must be a power of two for Reduction::BitMask",
+        })
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Enhanced double hashing hasher.
+///
+/// Emits an iterator (for a given input key) over hash values generated using
+/// enhanced double hashing.
+///
+/// The type of the second hash builder defaults to the type of the first one,
+/// e.g. `DoubleHashHasher<u64, MultiplyShiftBuilder>` uses two (differently
+/// seeded) [`MultiplyShiftBuilder`]s.
+#[derive(Clone, Copy)]
+pub struct DoubleHashHasher<T = u64, H1 = Xxh3Builder, H2 = H1> {
+    hash_builder1: H1,
+    hash_builder2: H2,
+    n: T,
+    truncation_mode: TruncationMode,
+    hashing_mode: HashingMode,
+    key_hashing: KeyHashing,
+    reduction: Reduction,
+    nonzero_h2: bool,
+    distinct: bool,
+    domain: Option<u64>,
+    constant_time: bool,
+
+    /// Barrett reciprocal of `n`, precomputed to avoid dividing for every key.
+    reciprocal: Option<u128>,
+
+    /// Hashes the key once with the first hash builder, providing both start
+    /// points (with [`KeyHashing::Double`], e.g. for HMAC, whose two halves
+    /// would otherwise be computed separately).
+    split_hash: Option<SplitHash<H1>>,
+}
+
+/// Hashes a key with the given hash builder, the key being written by the
+/// callback, and returns the words of both start points, as the low and the
+/// high halves.
+type SplitHash<H> = fn(&H, &mut dyn FnMut(&mut dyn hash::Hasher)) -> u128;
+
+impl DoubleHashHasher<u64, Xxh3Builder, Xxh3Builder> {
+    /// Constructs a new double hasher using default hash builders.
+    pub fn new() -> Self {
+        DoubleHashBuilder::new().build_hash_iter_hasher()
+    }
+
+    /// Constructs a new double hasher using default hash builders, seeded
+    /// with the given seeds (which should differ, see
+    /// [`DoubleHashBuilder::try_build_hash_iter_hasher`]).
+    pub fn with_seeds(seed1: u64, seed2: u64) -> Self {
+        Self::with_seeds_and_n(seed1, seed2, usize::MAX as u64)
+    }
+}
+
+impl<T: Number> DoubleHashHasher<T, Xxh3Builder, Xxh3Builder> {
+    /// Constructs a new double hasher using default hash builders, seeded
+    /// with the given seeds, and emitting hash values in `[0, n)`.
+    ///
+    /// ```
+    /// use hash_iter::{DoubleHashHasher, HashIterHasher};
+    ///
+    /// let hasher = DoubleHashHasher::with_seeds_and_n(1, 2, 1000u32);
+    /// assert!(hasher.hash_iter(&"hello", 3).all(|h| h < 1000));
+    /// ```
+    pub fn with_seeds_and_n(seed1: u64, seed2: u64, n: T) -> Self {
+        DoubleHashBuilder::new()
+            .with_u64_seeds(seed1, seed2)
+            .with_n(n)
+            .build_hash_iter_hasher()
+    }
+}
+
+impl Default for DoubleHashHasher<u64, Xxh3Builder, Xxh3Builder> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2> {
+    pub fn with_hash_builders(hash_builder1: H1, hash_builder2: H2, n: T) -> Self
+    where
+        T: sealed::Modulus,
+    {
+        Self {
+            hash_builder1,
+            hash_builder2,
+            reciprocal: n.reciprocal(),
+            n,
+            truncation_mode: TruncationMode::default(),
+            hashing_mode: HashingMode::default(),
+            key_hashing: KeyHashing::default(),
+            reduction: Reduction::default(),
+            nonzero_h2: false,
+            distinct: false,
+            domain: None,
+            constant_time: false,
+            split_hash: None,
+        }
+    }
+
+    /// Hashes keys once with the first hash builder, splitting the hash into
+    /// both start points, instead of hashing them with each hash builder.
+    ///
+    /// The start points must match the ones of the two hash builders, which
+    /// are still used by [`stream_hasher`](Self::stream_hasher).
+    #[cfg(feature = "hmac")]
+    pub(crate) fn with_split_hash(self, split_hash: SplitHash<H1>) -> Self {
+        Self {
+            split_hash: Some(split_hash),
+            ..self
+        }
+    }
+
+    /// Sets the strategy for converting 64-bit hashes into narrower types.
+    pub fn with_truncation_mode(self, truncation_mode: TruncationMode) -> Self {
+        Self {
+            truncation_mode,
+            ..self
+        }
+    }
+
+    /// Sets the recurrence used to generate hash points.
+    pub fn with_hashing_mode(self, hashing_mode: HashingMode) -> Self {
+        Self {
+            hashing_mode,
+            ..self
+        }
+    }
+
+    /// Sets the strategy for deriving the start points from the key.
+    pub fn with_key_hashing(self, key_hashing: KeyHashing) -> Self {
+        Self {
+            key_hashing,
+            ..self
+        }
+    }
+
+    /// Sets the method of mapping the start points into `[0, n)`.
+    ///
+    /// If the reduction is [`Reduction::BitMask`] (or [`Reduction::Auto`]),
+    /// and `n` is not a power of two, [`Reduction::Modulo`] is used instead.
+    pub fn with_reduction(self, reduction: impl Into<Reduction>) -> Self {
+        Self {
+            reduction: reduction.into(),
+            ..self
+        }
+    }
+
+    /// Forces the second start point into `[1, n)` (and to be odd, when `n`
+    /// is a power of two).
+    ///
+    /// If `h2` is `0 (mod n)`, the plain double hashing sequence is stuck on
+    /// a single slot (and the enhanced one covers much fewer slots than it
+    /// could). With an odd `h2` and power-of-two `n`, the plain sequence visits
+    /// all the slots. Disabled by default, so that existing sequences are not
+    /// changed.
+    pub fn with_nonzero_h2(self, nonzero_h2: bool) -> Self {
+        Self { nonzero_h2, ..self }
+    }
+
+    /// Guarantees that the first `min(k, n)` emitted hash values are pairwise
+    /// distinct (see
+    /// [`DoubleHashHasher::hash_iter_distinct`](DoubleHashHasher::hash_iter_distinct)
+    /// for how duplicates are skipped).
+    ///
+    /// Affects the [`HashIterHasher`] methods. Tracking the emitted values has
+    /// a cost, so this is only worth it for small tables, where duplicates are
+    /// frequent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the constant-time mode is enabled (see
+    /// [`with_constant_time`](Self::with_constant_time)).
+    pub fn with_distinct(self, distinct: bool) -> Self {
+        assert!(
+            !(distinct && self.constant_time),
+            "distinct hashes cannot be generated in constant time"
+        );
+        Self { distinct, ..self }
+    }
+
+    /// Sets the domain (namespace) tag, e.g. `"user-ids"`, folded into the
+    /// hashing of every key.
+    ///
+    /// Hashers of different domains produce independent sequences, even with
+    /// identical seeds, which saves keeping track of distinct seeds per
+    /// filter. The tag is hashed along with the key (or, for
+    /// [`KeyHashing::Integer`], mixed into the seed).
+    pub fn with_domain(self, domain: &str) -> Self {
+        Self {
+            domain: Some(domain_tag(domain)),
+            ..self
+        }
+    }
+
+    /// Generates the sequences without branching on secret values, for
+    /// contexts where probe timing could leak information about the keys
+    /// (e.g. password-derived identifiers checked against a filter).
+    ///
+    /// The start points are reduced with a branchless Barrett reduction
+    /// (instead of dividing them, as the division takes a data-dependent time
+    /// on some CPUs), and made odd or non-zero arithmetically, so that every
+    /// key takes the same work. The generated sequences are unchanged, and
+    /// advancing them is branchless anyway. Branches only depend on `n` and
+    /// the configuration.
+    ///
+    /// This is a best-effort guarantee: it covers the
+    /// [`hashes`](Self::hashes) and [`HashIterHasher`] methods for the
+    /// fixed-width output types, but not the hashing of the key itself (which
+    /// is up to the hash builders), nor what the caller does with the hash
+    /// values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`with_distinct`](Self::with_distinct) is enabled, since
+    /// skipping duplicates takes a key-dependent amount of work.
+    pub fn with_constant_time(self, constant_time: bool) -> Self {
+        assert!(
+            !(constant_time && self.distinct),
+            "distinct hashes cannot be generated in constant time"
+        );
+        Self {
+            constant_time,
+            ..self
+        }
+    }
+}
+
+impl<T, H1, H2> HashIterHasher<T> for DoubleHashHasher<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    fn hash_iter<K: hash::Hash + ?Sized>(&self, key: &K, count: usize) -> impl Iterator<Item = T> {
+        self.hash_iter_u64(key, count as u64)
+    }
+
+    fn hash_iter_u64<K: hash::Hash + ?Sized>(
+        &self,
+        key: &K,
+        count: u64,
+    ) -> impl Iterator<Item = T> {
+        if self.distinct {
+            HashIter::Distinct(self.distinct_hashes(key, count))
+        } else {
+            HashIter::Plain(self.hashes(key, count))
+        }
+    }
+
+    fn hash_iter_into<K: hash::Hash + ?Sized>(&self, key: &K, buf: &mut [T]) -> usize {
+        if self.distinct {
+            let hashes = self.distinct_hashes(key, buf.len() as u64);
+            return buf
+                .iter_mut()
+                .zip(hashes)
+                .map(|(slot, hash)| *slot = hash)
+                .count();
+        }
+        self.hashes(key, buf.len() as u64).fill(buf)
+    }
+
+    /// Writes `count` hash values for each of the `keys` into `out`, see
+    /// [`HashIterHasher::hash_iter_batch`].
+    ///
+    /// For the output types computed in 64 bits (`u64`, `usize` and `i64`),
+    /// the sequences of several keys are advanced at once, using AVX2 (on
+    /// x86-64) or NEON (on AArch64) when the running CPU supports them. The
+    /// keys themselves are still hashed one by one, which dominates the cost,
+    /// so this is not notably faster than per-key hashing. The output is the
+    /// same as with one [`hash_iter_into`](Self::hash_iter_into) call per key.
+    fn hash_iter_batch<K: hash::Hash>(&self, keys: &[K], count: usize, out: &mut [T]) {
+        let len = keys.len().checked_mul(count);
+        assert_eq!(len, Some(out.len()), "out must hold count hashes per key");
+        if count == 0 || self.wide_hash_iter_batch(keys, count, out, wide::Isa::detect()) {
+            return;
+        }
+        for (key, row) in keys.iter().zip(out.chunks_exact_mut(count)) {
+            self.hash_iter_into(key, row);
+        }
+    }
+
+    fn hash_array<const N: usize, K: hash::Hash + ?Sized>(&self, key: &K) -> [T; N] {
+        let mut hashes = [T::zero(); N];
+        self.hash_iter_into(key, &mut hashes);
+        hashes
+    }
+
+    fn hash_for_each<K, F>(&self, key: &K, count: usize, f: F)
+    where
+        K: hash::Hash + ?Sized,
+        F: FnMut(T),
+    {
+        if self.distinct {
+            return self.distinct_hashes(key, count as u64).for_each(f);
+        }
+        self.hashes(key, count as u64).visit(f)
+    }
+}
+
+/// Iterator returned by the [`HashIterHasher`] methods of
+/// [`DoubleHashHasher`], depending on whether distinct hash values are
+/// required.
+enum HashIter<T: Number> {
+    Plain(Hashes<T>),
+    Distinct(distinct::Distinct<T>),
+}
+
+impl<T: Number> Iterator for HashIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Plain(hashes) => hashes.next(),
+            Self::Distinct(hashes) => hashes.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Plain(hashes) => hashes.size_hint(),
+            Self::Distinct(hashes) => hashes.size_hint(),
+        }
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        match self {
+            Self::Plain(hashes) => hashes.last(),
+            Self::Distinct(hashes) => hashes.last(),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self {
+            Self::Plain(hashes) => hashes.nth(n),
+            Self::Distinct(hashes) => hashes.nth(n),
+        }
+    }
+}
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Returns the `i`-th hash value for the given key, without constructing
+    /// and consuming an iterator.
+    ///
+    /// Equivalent to `self.hash_iter_u64(key, i + 1).last()`, but computed
+    /// directly, in constant time.
+    pub fn hash_at<K: hash::Hash + ?Sized>(&self, key: &K, i: u64) -> T {
+        self.hashes(key, 0).hash_at(i)
+    }
+
+    /// Returns an iterator over `count` hash values for the given key.
+    ///
+    /// Unlike [`hash_iter`](HashIterHasher::hash_iter), the concrete iterator
+    /// type is returned, giving access to [`Hashes`]-specific methods (e.g.
+    /// [`Hashes::with_start`]).
+    pub fn hashes<K: hash::Hash + ?Sized>(&self, key: &K, count: u64) -> Hashes<T> {
+        self.hashes_mod(key, self.n.to_repr(), count)
+    }
+
+    /// Returns an iterator over `count` hash values for the given key, salted
+    /// with `salt`.
+    ///
+    /// Sequences of the same key under different salts (e.g. epochs, or
+    /// tenants) are independent, as if produced by differently seeded
+    /// hashers, so filters can be rotated without constructing a new hasher
+    /// per salt. Same as hashing the `(salt, key)` pair.
+    pub fn hash_iter_with_salt<K: hash::Hash + ?Sized>(
+        &self,
+        key: &K,
+        salt: u64,
+        count: usize,
+    ) -> impl Iterator<Item = T> {
+        let (key, count) = ((salt, key), count as u64);
+        if self.distinct {
+            HashIter::Distinct(self.distinct_hashes(&key, count))
+        } else {
+            HashIter::Plain(self.hashes(&key, count))
+        }
+    }
+
+    /// Returns an iterator over `count` hash values for the given bytes,
+    /// which are fed to the hash builders as they are.
+    ///
+    /// Unlike with `hash_iter(&bytes, ..)`, no length (or other prefix) is
+    /// hashed along with the bytes, so the start points match the hashes of
+    /// the same bytes computed elsewhere (e.g. in other languages). Same as
+    /// hashing [`RawBytes(bytes)`](RawBytes).
+    pub fn hash_iter_bytes(&self, bytes: &[u8], count: usize) -> impl Iterator<Item = T> {
+        let (key, count) = (RawBytes(bytes), count as u64);
+        if self.distinct {
+            HashIter::Distinct(self.distinct_hashes(&key, count))
+        } else {
+            HashIter::Plain(self.hashes(&key, count))
+        }
+    }
+
+    /// Returns an iterator over `count` hash values, out of the base hashes
+    /// `h1` and `h2` of a key hashed elsewhere (e.g. in another process).
+    ///
+    /// The base hashes stand for the two hashes of the key: they are truncated
+    /// and reduced into `[0, n)` as configured (the `nonzero_h2`, `distinct`
+    /// and constant-time settings apply as well), while the key hashing and
+    /// the domain are not used. Wider output types (e.g. `u128`) take them
+    /// zero-extended.
+    ///
+    /// ```
+    /// use {
+    ///     hash_iter::{DoubleHashHasher, HashIterHasher, RawBytes},
+    ///     xxhash_rust::xxh3::xxh3_64_with_seed,
+    /// };
+    ///
+    /// let hasher = DoubleHashHasher::<u64>::new();
+    /// let (h1, h2) = (
+    ///     xxh3_64_with_seed(b"hello", 12345),
+    ///     xxh3_64_with_seed(b"hello", 67890),
+    /// );
+    /// assert!(hasher
+    ///     .iter_from_hashes(h1, h2, 3)
+    ///     .eq(hasher.hash_iter(&RawBytes("hello"), 3)));
+    /// ```
+    pub fn iter_from_hashes(&self, h1: u64, h2: u64, count: usize) -> impl Iterator<Item = T> {
+        let mode = self.truncation_mode;
+        let word = |hash| {
+            move |i| {
+                if i == 0 {
+                    mode.apply::<T::Repr>(hash)
+                } else {
+                    0
+                }
+            }
+        };
+        let x = T::Repr::from_hash_words(word(h1));
+        let y = T::Repr::from_hash_words(word(h2));
+        let n = self.n.to_repr();
+        if self.distinct {
+            HashIter::Distinct(distinct::Distinct::new(
+                self.sequence(x, y, n, u64::MAX),
+                count as u64,
+            ))
+        } else {
+            HashIter::Plain(self.sequence(x, y, n, count as u64))
+        }
+    }
+
+    /// Returns an iterator over `count` hash values for a key hashed elsewhere
+    /// into the 64-bit `hash` (e.g. carried in a record header).
+    ///
+    /// The key is not hashed again: the two base hashes are derived out of
+    /// `hash` with independent SplitMix64 mixers (after folding the domain in,
+    /// if any), and then used as with
+    /// [`iter_from_hashes`](Self::iter_from_hashes).
+    pub fn hash_iter_prehashed(&self, hash: u64, count: usize) -> impl Iterator<Item = T> {
+        let hash = self.domain.map_or(hash, |tag| hash ^ tag);
+        self.iter_from_hashes(splitmix64(hash, 0), splitmix64(hash, 1), count)
+    }
+
+    /// Same as [`hashes`](Self::hashes), but skips the hasher's configuration
+    /// checks, for the lowest possible per-key cost.
+    ///
+    /// The start points are reduced with the plain modulo, and then only the
+    /// hashing mode is applied: the reduction, `nonzero_h2`, `distinct` and
+    /// constant-time settings are ignored. For the default configuration, the
+    /// generated sequence is the same as with [`hashes`](Self::hashes).
+    ///
+    /// # Safety
+    ///
+    /// The hasher's `n` must be non-zero (the full range is not supported).
+    pub unsafe fn hash_iter_unchecked<K: hash::Hash + ?Sized>(
+        &self,
+        key: &K,
+        count: u64,
+    ) -> Hashes<T> {
+        let (x, y) = self.base_hashes(key);
+        let n = self.n.to_repr();
+        // SAFETY: guaranteed by the caller.
+        unsafe { hint::assert_unchecked(!n.is_zero()) };
+        Hashes::from_repr(x % n, y % n, n, count).with_hashing_mode(self.hashing_mode)
+    }
+
+    /// Returns an iterator over `count` hash values for the given key, mapped
+    /// into the half-open `range` (instead of `[0, n)`).
+    ///
+    /// The hasher's `n` is ignored: hash values are computed modulo the width
+    /// of the range, and then offset by its start.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    pub fn hash_iter_range<K: hash::Hash + ?Sized>(
+        &self,
+        key: &K,
+        count: usize,
+        range: ops::Range<T>,
+    ) -> impl Iterator<Item = T> {
+        assert!(range.start < range.end, "range must not be empty");
+        let width = range.end - range.start;
+        self.hashes_mod(key, width.to_repr(), count as u64)
+            .map(move |hash| range.start + hash)
+    }
+
+    /// Returns an iterator over `count` uniformly distributed floats in
+    /// `[0, 1)` for the given key.
+    ///
+    /// Each float is built from the top 53 bits of a 64-bit hash value, so
+    /// that every representable multiple of `2^-53` is equally likely. The
+    /// hasher's `n` (and output type) is ignored: the underlying sequence is
+    /// always computed over the full `u64` range.
+    pub fn hash_iter_f64<K: hash::Hash + ?Sized>(
+        &self,
+        key: &K,
+        count: usize,
+    ) -> impl Iterator<Item = f64> {
+        const SCALE: f64 = 1.0 / (1u64 << 53) as f64;
+        let words = self.key_words(key);
+        let (x, y) = (words.word1(0), words.word2(0));
+        self.sequence::<u64>(x, y, u64::MAX, count as u64)
+            .map(|hash| (hash >> 11) as f64 * SCALE)
+    }
+
+    /// Returns an iterator over `count` `(quotient, remainder)` pairs for the
+    /// given key, for quotient filters of `table_size` slots storing
+    /// `remainder_bits`-bit remainders.
+    ///
+    /// Each 64-bit hash value is split: its low `remainder_bits` bits are the
+    /// remainder, and its high bits are mapped onto `[0, table_size)` by
+    /// multiplication (rather than by masking), so that the quotient is
+    /// uniform even if `table_size` is not a power of two. Like for
+    /// [`hash_iter_f64`](Self::hash_iter_f64), the hasher's `n` is ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `remainder_bits` is not less than 64, or if `table_size` is
+    /// zero or larger than `2^(64 - remainder_bits)` (i.e. some slots could
+    /// never be reached).
+    pub fn hash_iter_quotients<K: hash::Hash + ?Sized>(
+        &self,
+        key: &K,
+        count: usize,
+        table_size: u64,
+        remainder_bits: u32,
+    ) -> impl Iterator<Item = (u64, u64)> {
+        assert!(remainder_bits < 64, "remainder_bits must be less than 64");
+        let quotient_bits = 64 - remainder_bits;
+        assert!(
+            table_size > 0 && table_size as u128 <= 1 << quotient_bits,
+            "table_size must be in [1, 2^(64 - remainder_bits)]"
+        );
+
+        let words = self.key_words(key);
+        let (x, y) = (words.word1(0), words.word2(0));
+        let mask = (1u64 << remainder_bits) - 1;
+        self.sequence::<u64>(x, y, u64::MAX, count as u64)
+            .map(move |hash| {
+                let quotient =
+                    ((hash >> remainder_bits) as u128 * table_size as u128) >> quotient_bits;
+                (quotient as u64, hash & mask)
+            })
+    }
+
+    /// Same as [`hashes`](Self::hashes), but the start points are mapped into
+    /// `[0, n)` using the given reduction, instead of the configured one.
+    ///
+    /// Useful for custom [`RangeReduction`] implementations (the ones provided
+    /// by the crate can be configured with
+    /// [`DoubleHashBuilder::with_reduction`]).
+    pub fn hashes_with_reduction<K: hash::Hash + ?Sized>(
+        &self,
+        key: &K,
+        count: u64,
+        reduction: impl RangeReduction,
+    ) -> Hashes<T> {
+        let (x, y) = self.base_hashes(key);
+        self.reduced_sequence(x, y, self.n.to_repr(), count, &reduction)
+    }
+
+    fn hashes_mod<K: hash::Hash + ?Sized>(&self, key: &K, n: T::Repr, count: u64) -> Hashes<T> {
+        let (x, y) = self.base_hashes(key);
+        self.sequence(x, y, n, count)
+    }
+
+    /// Constructs the sequence out of the (unreduced) start points, applying
+    /// the hasher's configuration.
+    fn sequence<U: Number>(&self, x: U::Repr, y: U::Repr, n: U::Repr, count: u64) -> Hashes<U> {
+        if self.constant_time {
+            return self.constant_time_sequence(x, y, n, count);
+        }
+        if n.is_zero() {
+            // Full range: the start points need no reduction.
+            let y = if self.nonzero_h2 { odd(y) } else { y };
+            return Hashes::from_repr(x, y, n, count).with_hashing_mode(self.hashing_mode);
+        }
+        if self.reduction == Reduction::Modulo && !self.nonzero_h2 {
+            if let Some(m) = self.reciprocal_for::<U>(n) {
+                // Reduce using the precomputed reciprocal, without dividing.
+                let reduce = |v| if v >= n { barrett(v, n, m) } else { v };
+                return Hashes::from_repr(reduce(x), reduce(y), n, count)
+                    .with_hashing_mode(self.hashing_mode);
+            }
+        }
+
+        let masked = matches!(self.reduction, Reduction::BitMask | Reduction::Auto);
+        if masked && n > U::Repr::one() && is_power_of_two(n) {
+            // Any odd value is in `[1, n)`, no need to reduce modulo `n - 1`.
+            let (x, y) = (x.low_bits(n), odd(y.low_bits(n)));
+            return Hashes::from_repr(x, y, n, count).with_hashing_mode(self.hashing_mode);
+        }
+        self.reduced_sequence(x, y, n, count, &self.reduction)
+    }
+
+    /// Same as [`sequence`](Self::sequence), but without branching on the
+    /// start points, nor dividing them (see
+    /// [`with_constant_time`](Self::with_constant_time)). The sequence is the
+    /// same.
+    fn constant_time_sequence<U: Number>(
+        &self,
+        x: U::Repr,
+        y: U::Repr,
+        n: U::Repr,
+        count: u64,
+    ) -> Hashes<U> {
+        let one = U::Repr::one();
+        // Adds one to even values (branches on public values only: `n` and the
+        // configuration).
+        let odd = |v: U::Repr| v + (one - v.low_bits(one + one));
+        let (x, y) = if n.is_zero() {
+            (x, if self.nonzero_h2 { odd(y) } else { y })
+        } else if matches!(self.reduction, Reduction::BitMask | Reduction::Auto)
+            && n > one
+            && is_power_of_two(n)
+        {
+            (x.low_bits(n), odd(y.low_bits(n)))
+        } else {
+            let reduce = |v: U::Repr, n: U::Repr| match self.reduction {
+                Reduction::Lemire => v.mul_high(n),
+                Reduction::Identity => v,
+                _ => ct_rem(v, n),
+            };
+            let y = match self.nonzero_h2 {
+                false => reduce(y, n),
+                true if n == one => U::Repr::zero(),
+                // Same as `nonzero_step()`: `y - 1` for even `y`.
+                true if is_power_of_two(n) => {
+                    let y = one + reduce(y, n - one);
+                    y - (one - y.low_bits(one + one))
+                }
+                true => one + reduce(y, n - one),
+            };
+            (reduce(x, n), y)
+        };
+        Hashes::from_repr(x, y, n, count).with_hashing_mode(self.hashing_mode)
+    }
+
+    /// Returns the precomputed reciprocal of `n`, if `n` is the hasher's own
+    /// modulus (in a representation type of the same width).
+    fn reciprocal_for<U: Number>(&self, n: U::Repr) -> Option<U::Repr> {
+        let same_width = U::Repr::max_value().to_u128() == T::Repr::max_value().to_u128();
+        let same_n = n.to_u128() == self.n.to_repr().to_u128();
+        self.reciprocal
+            .filter(|_| same_width && same_n)
+            .and_then(U::Repr::from_u128)
+    }
+
+    /// Same as [`sequence`](Self::sequence), with the start points reduced
+    /// using the given reduction.
+    fn reduced_sequence<U: Number>(
+        &self,
+        x: U::Repr,
+        y: U::Repr,
+        n: U::Repr,
+        count: u64,
+        reduction: &impl RangeReduction,
+    ) -> Hashes<U> {
+        let x = reduction.reduce(x, n);
+        let y = if self.nonzero_h2 {
+            nonzero_step(y, n, reduction)
+        } else {
+            reduction.reduce(y, n)
+        };
+        Hashes::from_repr(x, y, n, count).with_hashing_mode(self.hashing_mode)
+    }
+
+    /// Hashes the key with both hash builders, returning the (unreduced)
+    /// start points of the sequence.
+    fn base_hashes<K: hash::Hash + ?Sized>(&self, key: &K) -> (T::Repr, T::Repr) {
+        let mode = self.truncation_mode;
+        let words = self.key_words(key);
+        let x = T::Repr::from_hash_words(|i| mode.apply::<T::Repr>(words.word1(i)));
+        let y = T::Repr::from_hash_words(|i| mode.apply::<T::Repr>(words.word2(i)));
+        (x, y)
+    }
+}
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Returns the source of the hash words of the key.
+    fn key_words<'a, K: hash::Hash + ?Sized>(&'a self, key: &'a K) -> KeyWords<'a, K, H1, H2> {
+        KeyWords {
+            hash_builder1: &self.hash_builder1,
+            hash_builder2: &self.hash_builder2,
+            key,
+            key_hashing: self.key_hashing,
+            domain: self.domain,
+            split_hash: self.split_hash,
+            last: Cell::new(None),
+        }
+    }
+}
+
+/// Source of the 64-bit hash words of a key, out of which the two start
+/// points of the sequence are built.
+struct KeyWords<'a, K: ?Sized, H1, H2> {
+    hash_builder1: &'a H1,
+    hash_builder2: &'a H2,
+    key: &'a K,
+    key_hashing: KeyHashing,
+    domain: Option<u64>,
+    split_hash: Option<SplitHash<H1>>,
+
+    /// The last hash computed (with its word index), so that both start
+    /// points are built out of a single hash of the key.
+    last: Cell<Option<(usize, u128)>>,
+}
+
+impl<K, H1, H2> KeyWords<'_, K, H1, H2>
+where
+    K: hash::Hash + ?Sized,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Returns the `i`-th hash word of the first start point.
+    #[inline]
+    fn word1(&self, i: usize) -> u64 {
+        match self.key_hashing {
+            KeyHashing::Double => match self.split_hash {
+                Some(split) => self.cached(i, || self.split(split, i)) as u64,
+                None => self.hash_word(self.hash_builder1, i),
+            },
+            KeyHashing::Single128 { seed } => self.cached(i, || self.xxh3_128(seed, i)) as u64,
+            KeyHashing::Mixed => self.cached(i, || self.word(i)) as u64,
+            KeyHashing::Integer { seed } => {
+                splitmix64(self.integer() ^ self.seed(seed), 2 * i as u64)
+            }
+            #[cfg(feature = "sha2")]
+            KeyHashing::Sha256 => self.cached(i, || self.sha256(i)) as u64,
+            #[cfg(feature = "keccak")]
+            KeyHashing::Keccak256 => self.cached(i, || self.keccak256(i)) as u64,
+        }
+    }
+
+    /// Returns the `i`-th hash word of the second start point.
+    #[inline]
+    fn word2(&self, i: usize) -> u64 {
+        match self.key_hashing {
+            KeyHashing::Double => match self.split_hash {
+                Some(split) => (self.cached(i, || self.split(split, i)) >> 64) as u64,
+                None => self.hash_word(self.hash_builder2, i),
+            },
+            KeyHashing::Single128 { seed } => {
+                (self.cached(i, || self.xxh3_128(seed, i)) >> 64) as u64
+            }
+            KeyHashing::Mixed => mix64(self.cached(i, || self.word(i)) as u64),
+            KeyHashing::Integer { seed } => {
+                splitmix64(self.integer() ^ self.seed(seed), 2 * i as u64 + 1)
+            }
+            #[cfg(feature = "sha2")]
+            KeyHashing::Sha256 => (self.cached(i, || self.sha256(i)) >> 64) as u64,
+            #[cfg(feature = "keccak")]
+            KeyHashing::Keccak256 => (self.cached(i, || self.keccak256(i)) >> 64) as u64,
+        }
+    }
+
+    /// Returns the value of an integer key (or a folding of other keys).
+    #[inline]
+    fn integer(&self) -> u64 {
+        let mut hasher = IntegerHasher(0);
+        hash::Hash::hash(self.key, &mut hasher);
+        hash::Hasher::finish(&hasher)
+    }
+
+    /// Returns the `i`-th hash of the key, reusing the last one computed if
+    /// possible.
+    fn cached(&self, i: usize, hash: impl FnOnce() -> u128) -> u128 {
+        if let Some((j, word)) = self.last.get() {
+            if i == j {
+                return word;
+            }
+        }
+
+        let word = hash();
+        self.last.set(Some((i, word)));
+        word
+    }
+
+    /// Returns the `i`-th hash word of the key, using the first hash builder.
+    fn word(&self, i: usize) -> u128 {
+        self.hash_word(self.hash_builder1, i).into()
+    }
+
+    /// Returns the `i`-th hash word of the key (tagged with the domain, if
+    /// any), using the given hash builder.
+    #[inline]
+    fn hash_word<H: hash::BuildHasher>(&self, hash_builder: &H, i: usize) -> u64 {
+        match self.domain {
+            Some(tag) => hash_word(hash_builder, &(tag, self.key), i),
+            None => hash_word(hash_builder, self.key, i),
+        }
+    }
+
+    /// Returns the seed of the integer key hashing, with the domain mixed in.
+    #[inline]
+    fn seed(&self, seed: u64) -> u64 {
+        self.domain.map_or(seed, |tag| seed ^ tag)
+    }
 
-/// Provides an iterator over multiple hash values for a given key.
-pub trait HashIterHasher<T> {
-    /// Returns an iterator over `count` number of hash values generated using
-    /// enhanced double hashing.
-    fn hash_iter<K: hash::Hash + ?Sized>(&self, key: &K, count: usize) -> impl Iterator<Item = T>;
-}
+    /// Returns the words of both start points out of the `i`-th hash of the
+    /// key, using the first hash builder.
+    fn split(&self, split: SplitHash<H1>, i: usize) -> u128 {
+        split(self.hash_builder1, &mut |hasher| {
+            self.feed(hasher, i);
+        })
+    }
 
-/// Builds hash iterator hasher -- a hasher capable of generating multiple hash
-/// values.
-pub trait BuildHashIterHasher<T> {
-    type Hasher: HashIterHasher<T>;
+    /// Returns the `i`-th 128-bit XXH3 hash word of the key.
+    fn xxh3_128(&self, seed: u64, i: usize) -> u128 {
+        self.feed(Xxh3::with_seed(seed), i).digest128()
+    }
 
-    fn build_hash_iter_hasher(&self) -> Self::Hasher;
+    /// Returns the leading words of both halves of the `i`-th SHA-256 digest
+    /// of the key, as the low and the high halves.
+    #[cfg(feature = "sha2")]
+    fn sha256(&self, i: usize) -> u128 {
+        let hasher = hash::BuildHasher::build_hasher(&Sha256Builder::new());
+        self.feed(hasher, i).digest_words()
+    }
+
+    /// Same as [`sha256`](Self::sha256), with Keccak-256.
+    #[cfg(feature = "keccak")]
+    fn keccak256(&self, i: usize) -> u128 {
+        let hasher = hash::BuildHasher::build_hasher(&Keccak256Builder::new());
+        self.feed(hasher, i).digest_words()
+    }
+
+    /// Feeds the key (tagged with the domain, if any, and followed by the word
+    /// index `i`, if not the first one) to `hasher`, the same way as
+    /// `hash_word()` does.
+    fn feed<S: hash::Hasher>(&self, mut hasher: S, i: usize) -> S {
+        if let Some(tag) = self.domain {
+            hash::Hash::hash(&tag, &mut hasher);
+        }
+        if i == 0 {
+            hash::Hash::hash(self.key, &mut hasher);
+        } else {
+            hash::Hash::hash(&(self.key, i), &mut hasher);
+        }
+        hasher
+    }
 }
 
-/// Holds the state for the hasher that implements enhanced double hashing.
+/// Key wrapper hashing as the raw bytes of the wrapped value.
 ///
-/// Serves as a builder, allowing to configure the hasher with custom seeds,
-/// number of required hashes, and the size of the hash table.
-#[derive(Clone, Copy)]
-pub struct DoubleHashBuilder<T: Number = u64> {
-    seed1: T,
-    seed2: T,
-    n: T,
+/// Standard [`Hash`](hash::Hash) implementations of strings and slices write
+/// more than their bytes (a terminator, or the length), so that composite keys
+/// do not collide. This wrapper writes the bytes only, so that hash values
+/// match the ones computed by other implementations (e.g. of MurmurHash3) over
+/// the same bytes. It should only be used for keys that are not part of
+/// composite keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RawBytes<B>(pub B);
+
+impl<B: AsRef<[u8]>> hash::Hash for RawBytes<B> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        state.write(self.0.as_ref());
+    }
 }
 
-impl<T: Number> DoubleHashBuilder<T> {
-    /// Constructs a new hash iterator builder, with default seeds.
-    pub fn new() -> Self {
-        // Seeds for double hashing: essentially, we can use any seeds, to
-        // initialize the hasher (by default XXH3 uses `0`).
-        let seed1 = num_traits::FromPrimitive::from_u64(12345).expect("cannot create seed1");
-        let seed2 = num_traits::FromPrimitive::from_u64(67890).expect("cannot create seed2");
-        let n = num_traits::FromPrimitive::from_u64(usize::MAX as u64).expect("cannot create n");
-        Self { seed1, seed2, n }
+/// Hasher capturing the value of integer keys, see [`KeyHashing::Integer`].
+///
+/// Each written integer is folded into the state as `mix64(state) + value`,
+/// so a single written integer is captured as is (`mix64(0) = 0`). Bytes are
+/// folded as 64-bit little-endian words.
+struct IntegerHasher(u64);
+
+impl hash::Hasher for IntegerHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.write_u64(u64::from_le_bytes(word));
+        }
     }
 
-    pub fn with_seed1(self, seed1: T) -> Self {
-        Self { seed1, ..self }
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.write_u64(i.into());
     }
 
-    pub fn with_seed2(self, seed2: T) -> Self {
-        Self { seed2, ..self }
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write_u64(i.into());
     }
 
-    pub fn with_n(self, n: T) -> Self {
-        Self { n, ..self }
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.write_u64(i.into());
     }
-}
 
-impl<T: Number> Default for DoubleHashBuilder<T> {
-    fn default() -> Self {
-        Self::new()
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.0 = mix64(self.0).wrapping_add(i);
     }
-}
 
-impl<T: Number> BuildHashIterHasher<T> for DoubleHashBuilder<T> {
-    type Hasher = DoubleHashHasher<T, Xxh3Builder, Xxh3Builder>;
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.write_u64(i as u64);
+        self.write_u64((i >> 64) as u64);
+    }
 
-    fn build_hash_iter_hasher(&self) -> Self::Hasher {
-        let seed1 = num_traits::ToPrimitive::to_u64(&self.seed1).expect("cannot create seed1");
-        let seed2 = num_traits::ToPrimitive::to_u64(&self.seed2).expect("cannot create seed2");
-        DoubleHashHasher::with_hash_builders(
-            Xxh3Builder::new().with_seed(seed1),
-            Xxh3Builder::new().with_seed(seed2),
-            self.n,
-        )
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
     }
 }
 
-/// Enhanced double hashing hasher.
-///
-/// Emits an iterator (for a given input key) over hash values generated using
-/// enhanced double hashing.
-#[derive(Clone, Copy)]
-pub struct DoubleHashHasher<T = u64, H1 = Xxh3Builder, H2 = Xxh3Builder> {
-    hash_builder1: H1,
-    hash_builder2: H2,
-    n: T,
+/// SplitMix64 finalizer: a bijective mixing function, which makes every bit of
+/// the output depend on every bit of the input.
+#[inline]
+fn mix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
 }
 
-impl DoubleHashHasher<u64, Xxh3Builder, Xxh3Builder> {
-    /// Constructs a new double hasher using default hash builders.
-    pub fn new() -> Self {
-        DoubleHashBuilder::new().build_hash_iter_hasher()
-    }
+/// Returns the `i`-th output of the SplitMix64 generator seeded with `seed`,
+/// used to derive any number of independent-looking seeds out of one.
+#[inline]
+fn splitmix64(seed: u64, i: u64) -> u64 {
+    const GOLDEN_GAMMA: u64 = 0x9e3779b97f4a7c15;
+    mix64(seed.wrapping_add(i.wrapping_add(1).wrapping_mul(GOLDEN_GAMMA)))
 }
 
-impl<T, H1, H2> DoubleHashHasher<T, H1, H2> {
-    pub fn with_hash_builders(hash_builder1: H1, hash_builder2: H2, n: T) -> Self {
-        Self {
-            hash_builder1,
-            hash_builder2,
-            n,
-        }
-    }
+/// Returns the tag of the domain, out of which hashers of different domains
+/// derive independent sequences.
+fn domain_tag(domain: &str) -> u64 {
+    xxh3::xxh3_64(domain.as_bytes())
 }
 
-impl<T, H1, H2> HashIterHasher<T> for DoubleHashHasher<T, H1, H2>
+/// Returns the `i`-th 64-bit hash word of the `key`.
+///
+/// The first word is the plain hash of the key, subsequent words are obtained
+/// by hashing the key together with the word index.
+fn hash_word<H, K>(hash_builder: &H, key: &K, i: usize) -> u64
 where
-    T: Number,
-    H1: hash::BuildHasher,
-    H2: hash::BuildHasher,
+    H: hash::BuildHasher,
+    K: hash::Hash + ?Sized,
 {
-    fn hash_iter<K: hash::Hash + ?Sized>(&self, key: &K, count: usize) -> impl Iterator<Item = T> {
-        let hash1 = self.hash_builder1.hash_one(key);
-        let hash2 = self.hash_builder2.hash_one(key);
-
-        let x = num_traits::FromPrimitive::from_u64(hash1).expect("invalid hash point");
-        let y = num_traits::FromPrimitive::from_u64(hash2).expect("invalid hash point");
-        let count = num_traits::FromPrimitive::from_usize(count).expect("invalid count");
-        Hashes::new(x, y, self.n, count)
+    if i == 0 {
+        hash_builder.hash_one(key)
+    } else {
+        hash_builder.hash_one((key, i))
     }
 }
 
@@ -149,23 +2282,40 @@ where
 /// h(i) = h1(k) + i * h2(k) + (i^3-i)/6 (mod n)
 /// ```
 ///
+/// All computations are done using overflow-safe modular arithmetic, so the
+/// sequence follows the formula exactly, for any `n`. Since `h(i)` has a closed
+/// form, the iterator can be consumed from the back as well, and skipped ahead
+/// (see [`Iterator::nth`]) in constant time, without walking the whole
+/// sequence.
+///
 /// [1]: https://www.khoury.northeastern.edu/~pete/pub/bloom-filters-verification.pdf
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Hashes<T: Number> {
-    /// The first hash point.
-    hash1: T,
+    /// The first hash point, reduced modulo `n`.
+    hash1: T::Repr,
 
-    /// The second hash point.
-    hash2: T,
+    /// The second hash point, reduced modulo `n`.
+    hash2: T::Repr,
 
     /// The size of the hash table.
-    n: T,
+    n: T::Repr,
+
+    /// The number of hash points to generate (decremented when iterating from
+    /// the back).
+    k: u64,
 
-    /// The number of hash points to generate.
-    k: T,
+    /// The current number of hash points generated (from the front).
+    cnt: u64,
 
-    /// The current number of hash points generated.
-    cnt: T,
+    /// The last hash point generated from the front (before the first one,
+    /// `h1 - h2`, i.e. one step back from `h1`).
+    x: T::Repr,
+
+    /// The difference between the next and the last generated hash points.
+    y: T::Repr,
+
+    /// The recurrence used to generate hash points.
+    mode: HashingMode,
 }
 
 impl<T> Hashes<T>
@@ -175,16 +2325,286 @@ where
     /// Constructs a new hash iterator.
     ///
     /// The iterator is configured with the given starting hash points, for the
-    /// hashmap of size `n`, with expected number of generated hash points
-    /// equal to `k`.
+    /// hashmap of size `n` (`0` standing for the whole range of `T`), with
+    /// expected number of generated hash points equal to `k`.
+    ///
+    /// The starting hash points are reduced modulo `n` right away, so the
+    /// iterator is ready for both sequential and random access (see
+    /// [`hash_at`](Self::hash_at)) as soon as it is constructed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is negative. Counts past `u64::MAX` (for wider types) are
+    /// capped, see [`new_u64`](Self::new_u64).
     pub fn new(hash1: T, hash2: T, n: T, k: T) -> Self {
+        Self::new_u64(hash1, hash2, n, count_to_u64(k))
+    }
+
+    /// Same as [`new`](Self::new), but with a 64-bit count, so that long
+    /// sequences can be requested identically for any output type (and on
+    /// any target).
+    pub fn new_u64(hash1: T, hash2: T, n: T, k: u64) -> Self {
+        let n = n.to_repr();
+        let (hash1, hash2) = (rem(hash1.to_repr(), n), rem(hash2.to_repr(), n));
+        Self::from_repr(hash1, hash2, n, k).with_start(0)
+    }
+
+    /// Same as [`new_u64`](Self::new_u64), but skips reducing the starting
+    /// hash points.
+    ///
+    /// # Safety
+    ///
+    /// `n` must be non-zero (the full range is not supported), and both
+    /// `hash1` and `hash2` must be less than `n`. Otherwise the generated hash
+    /// points are not guaranteed to be in `[0, n)`, which code indexing
+    /// with them may rely upon.
+    pub unsafe fn new_unchecked(hash1: T, hash2: T, n: T, k: u64) -> Self {
+        let (hash1, hash2, n) = (hash1.to_repr(), hash2.to_repr(), n.to_repr());
+        // SAFETY: guaranteed by the caller.
+        unsafe { hint::assert_unchecked(!n.is_zero() && hash1 < n && hash2 < n) };
+        Self::from_repr(hash1, hash2, n, k).with_start(0)
+    }
+
+    /// Returns the first starting hash point, reduced modulo `n`.
+    ///
+    /// Together with [`hash2`](Self::hash2), [`n`](Self::n), [`k`](Self::k)
+    /// and [`hashing_mode`](Self::hashing_mode), this is enough to reconstruct
+    /// the very same sequence with [`Hashes::new_u64`].
+    pub fn hash1(&self) -> T {
+        T::from_repr(self.hash1)
+    }
+
+    /// Returns the second starting hash point, reduced modulo `n`.
+    pub fn hash2(&self) -> T {
+        T::from_repr(self.hash2)
+    }
+
+    /// Returns the size of the hash table (`0` stands for the whole range of
+    /// the output type).
+    pub fn n(&self) -> T {
+        T::from_repr(self.n)
+    }
+
+    /// Returns the number of hash points in the sequence.
+    ///
+    /// When the iterator is consumed from the back, this is decremented
+    /// accordingly, i.e. it is always the index one past the last hash point
+    /// still to be generated.
+    pub fn k(&self) -> u64 {
+        self.k
+    }
+
+    /// Returns the recurrence used to generate hash points.
+    pub fn hashing_mode(&self) -> HashingMode {
+        self.mode
+    }
+
+    /// Sets the recurrence used to generate hash points (enhanced by
+    /// default).
+    ///
+    /// The mode can be switched at any point: the remaining hash points are
+    /// generated as if the whole sequence had been generated in the new mode.
+    pub fn with_hashing_mode(self, mode: HashingMode) -> Self {
+        let position = self.cnt;
+        Self { mode, ..self }.with_start(position)
+    }
+
+    /// Returns the index of the next hash point to be generated (from the
+    /// front), i.e. the number of hash points generated so far.
+    pub fn position(&self) -> u64 {
+        self.cnt
+    }
+
+    /// Returns the number of hash points still to be generated.
+    pub fn remaining(&self) -> u64 {
+        self.k - self.cnt
+    }
+
+    /// Positions the iterator so that the next generated hash point is the
+    /// `start`-th one (counting from the start of the sequence).
+    ///
+    /// The state is computed directly, in constant time, so that partially
+    /// consumed sequences can be resumed without replaying them. If `start` is
+    /// past the end of the sequence, the iterator is exhausted.
+    pub fn with_start(self, start: u64) -> Self {
+        let start = start.min(self.k);
+        let (x, y) = match start.checked_sub(1) {
+            Some(i) => (self.point_at(i), self.delta_at(i)),
+            // One step back from the first hash point, so that advancing from
+            // here yields `h1` (the step added to `y` is zero at this point).
+            None => {
+                let y = self.first_delta();
+                (sub_mod(self.hash1, y, self.n), y)
+            }
+        };
+
+        Self {
+            x,
+            y,
+            cnt: start,
+            ..self
+        }
+    }
+
+    /// Splits the remaining hash points into two independent iterators: the
+    /// first one generates the next `mid` hash points, the second one
+    /// generates the rest.
+    ///
+    /// Both iterators are positioned in constant time, so they can be handed
+    /// over to different threads. If `mid` exceeds the number of remaining
+    /// hash points, the second iterator is empty.
+    pub fn split_at(self, mid: u64) -> (Self, Self) {
+        let mid = self.cnt + mid.min(self.remaining());
+        let head = Self {
+            k: mid,
+            ..self.clone()
+        };
+        (head, self.with_start(mid))
+    }
+
+    /// Returns an iterator over independent iterators, each generating (at
+    /// most) `size` consecutive hash points of the remaining sequence.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn chunks(self, size: u64) -> impl Iterator<Item = Self> {
+        assert!(size > 0, "chunk size must be non-zero");
+        let mut rest = Some(self);
+        iter::from_fn(move || {
+            let (head, tail) = rest.take().filter(|r| r.remaining() > 0)?.split_at(size);
+            rest = Some(tail);
+            Some(head)
+        })
+    }
+
+    /// Writes the next hash points into `buf`, until either the buffer is full
+    /// or the iterator is exhausted.
+    ///
+    /// Returns the number of hash points written. This is a tight loop, which
+    /// avoids per-item overhead of [`Iterator::next`].
+    pub fn fill(&mut self, buf: &mut [T]) -> usize {
+        let len = (buf.len() as u64).min(self.remaining()) as usize;
+        for slot in &mut buf[..len] {
+            *slot = T::from_repr(self.advance());
+        }
+
+        len
+    }
+
+    /// Invokes `f` for each of the remaining hash points, in a tight loop.
+    fn visit(&mut self, mut f: impl FnMut(T)) {
+        while self.cnt < self.k {
+            f(T::from_repr(self.advance()));
+        }
+    }
+
+    /// Returns the `i`-th hash point of the sequence, computed directly.
+    ///
+    /// The index is counted from the start of the sequence, regardless of how
+    /// far the iterator has been advanced (and it is not limited by `k`).
+    pub fn hash_at(&self, i: u64) -> T {
+        T::from_repr(self.point_at(i))
+    }
+
+    /// Constructs a new hash iterator from values already converted into the
+    /// representation type, with the start points already reduced modulo `n`.
+    ///
+    /// The state is left unset: it must be positioned with
+    /// [`with_start`](Self::with_start) (or
+    /// [`with_hashing_mode`](Self::with_hashing_mode)) before iterating.
+    fn from_repr(hash1: T::Repr, hash2: T::Repr, n: T::Repr, k: u64) -> Self {
         Self {
             hash1,
             hash2,
             n,
             k,
-            cnt: T::zero(),
+            cnt: 0,
+            x: T::Repr::zero(),
+            y: T::Repr::zero(),
+            mode: HashingMode::default(),
+        }
+    }
+}
+
+impl<T> Hashes<T>
+where
+    T: Number,
+{
+    /// Advances the sequence by one step, returning the new hash point.
+    #[inline]
+    fn advance(&mut self) -> T::Repr {
+        self.x = add_mod(self.x, self.y, self.n);
+        if self.mode == HashingMode::Enhanced {
+            self.y = add_mod(self.y, self.step(), self.n);
+        }
+        self.cnt += 1;
+        self.x
+    }
+
+    /// Returns the current count as the representation type, reduced modulo
+    /// `n`.
+    #[inline]
+    fn step(&self) -> T::Repr {
+        from_u64_mod(self.cnt, self.n)
+    }
+
+    /// Returns the difference between the second and the first hash points:
+    /// `h2 (mod n)`, or the stride in the linear mode.
+    #[inline]
+    fn first_delta(&self) -> T::Repr {
+        match self.mode {
+            HashingMode::Linear { stride } => from_u64_mod(stride, self.n),
+            _ => self.hash2,
+        }
+    }
+
+    /// Computes the `i`-th hash point directly, using the closed-form formula:
+    /// `h(i) = h1 + i * h2 + (i^3-i)/6 (mod n)` (without the cubic term in the
+    /// plain mode).
+    fn point_at(&self, i: u64) -> T::Repr {
+        let n = self.n;
+        let x = self.hash1;
+        let y = self.first_delta();
+        if i == 0 {
+            return x;
+        }
+
+        let linear = from_u64_mod(i, n).mul_mod(y, n);
+        if self.mode != HashingMode::Enhanced {
+            return add_mod(x, linear, n);
+        }
+
+        // `(i^3-i)/6 = (i-1) * i * (i+1) / 6`: out of three consecutive numbers
+        // one is divisible by `3`, and one of the first two is divisible by `2`,
+        // so the division is done before multiplying to avoid overflow (the
+        // factors are computed in 128 bits, as `i + 1` overflows for
+        // `i = u64::MAX`).
+        let i = u128::from(i);
+        let mut f = [i - 1, i, i + 1];
+        f[((i + 1) % 2) as usize] /= 2;
+        f[((3 - (i - 1) % 3) % 3) as usize] /= 3;
+        let [a, b, c] = f.map(|v| from_u128_mod(v, n));
+        let cubic = a.mul_mod(b, n).mul_mod(c, n);
+        add_mod(add_mod(x, linear, n), cubic, n)
+    }
+
+    /// Computes the difference between the `(i+1)`-th and the `i`-th hash
+    /// points directly: `h2 + i * (i+1)/2 (mod n)` (or just `h2` in the plain
+    /// mode).
+    fn delta_at(&self, i: u64) -> T::Repr {
+        let n = self.n;
+        let y = self.first_delta();
+        if self.mode != HashingMode::Enhanced {
+            return y;
         }
+
+        // One of two consecutive numbers is even, divide it before multiplying.
+        let i = u128::from(i);
+        let mut f = [i, i + 1];
+        f[(i % 2) as usize] /= 2;
+        let [a, b] = f.map(|v| from_u128_mod(v, n));
+        add_mod(y, a.mul_mod(b, n), n)
     }
 }
 
@@ -201,16 +2621,113 @@ where
             return None;
         }
 
-        if self.cnt == T::zero() {
-            self.cnt = self.cnt.add(T::one());
-            return Some(self.hash1.rem(self.n));
+        Some(T::from_repr(self.advance()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.k - self.cnt) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    /// Consumes the remaining hash points in a tight loop, without wrapping
+    /// each of them into an `Option`.
+    fn for_each<F: FnMut(Self::Item)>(mut self, f: F) {
+        self.visit(f);
+    }
+
+    fn fold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut acc = init;
+        while self.cnt < self.k {
+            acc = f(acc, T::from_repr(self.advance()));
+        }
+        acc
+    }
+
+    /// Available with the `nightly` feature (overriding `try_fold` requires
+    /// the unstable `Try` trait).
+    #[cfg(feature = "nightly")]
+    fn try_fold<B, F, R>(&mut self, init: B, mut f: F) -> R
+    where
+        F: FnMut(B, Self::Item) -> R,
+        R: ops::Try<Output = B>,
+    {
+        let mut acc = init;
+        while self.cnt < self.k {
+            acc = f(acc, T::from_repr(self.advance()))?;
+        }
+        R::from_output(acc)
+    }
+
+    /// Jumps directly to the `n`-th next hash point, in constant time.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let i = match self.cnt.checked_add(n as u64) {
+            Some(i) if i < self.k => i,
+            _ => {
+                self.cnt = self.k;
+                return None;
+            }
+        };
+
+        // Restore the state as if the sequence had been walked up to `i`.
+        self.x = self.point_at(i);
+        self.y = self.delta_at(i);
+        self.cnt = i + 1;
+
+        Some(T::from_repr(self.x))
+    }
+}
+
+/// Once exhausted, the iterator keeps returning `None`.
+impl<T> iter::FusedIterator for Hashes<T> where T: Number {}
+
+/// The number of remaining hash points is tracked as `u64`, so it is only
+/// guaranteed to fit into `usize` on 64-bit (or wider) targets. On narrower
+/// targets, [`Iterator::size_hint`] saturates instead.
+#[cfg(not(any(target_pointer_width = "16", target_pointer_width = "32")))]
+impl<T> ExactSizeIterator for Hashes<T> where T: Number {}
+
+/// Available with the `nightly` feature.
+///
+/// # Safety
+///
+/// The `size_hint` is exact: it reports the number of remaining hash points,
+/// and the upper bound is `None` only if that number exceeds `usize::MAX`.
+#[cfg(feature = "nightly")]
+unsafe impl<T> iter::TrustedLen for Hashes<T> where T: Number {}
+
+impl<T> DoubleEndedIterator for Hashes<T>
+where
+    T: Number,
+{
+    /// Returns the last not yet generated hash point, computed directly using
+    /// the closed-form formula (without walking the sequence).
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.cnt == self.k {
+            return None;
         }
 
-        self.hash1 = self.hash1.wrapping_add(&self.hash2).rem(self.n);
-        self.hash2 = self.hash2.wrapping_add(&self.cnt).rem(self.n);
-        self.cnt = self.cnt.add(T::one());
+        self.k -= 1;
+        Some(T::from_repr(self.point_at(self.k)))
+    }
+
+    /// Jumps directly to the `n`-th hash point from the back, in constant time.
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if (n as u64) >= self.k - self.cnt {
+            self.k = self.cnt;
+            return None;
+        }
 
-        Some(self.hash1)
+        self.k -= n as u64 + 1;
+        Some(T::from_repr(self.point_at(self.k)))
     }
 }
 
@@ -267,7 +2784,202 @@ mod tests {
         let k = 100;
         let mut iter = Hashes::new(hash1, hash2, n, k);
         for i in 0..k {
-            assert_eq!(iter.next(), Some(hasn_fn(i as u64, hash1, hash2, n)));
+            assert_eq!(iter.next(), Some(hasn_fn(i, hash1, hash2, n)));
+        }
+    }
+
+    #[test]
+    fn hashes_no_overflow_near_max() {
+        // Exact computation in `u128`, to validate modular arithmetic when the
+        // sums overflow `u64`.
+        fn exact(i: u64, hash1: u64, hash2: u64, n: u64) -> u64 {
+            let (i, n) = (i as u128, n as u128);
+            let x = hash1 as u128 % n;
+            let y = hash2 as u128 % n;
+            ((x + i * y % n + (i.pow(3) - i) / 6 % n) % n) as u64
+        }
+
+        let hash1 = u64::MAX - 1;
+        let hash2 = u64::MAX - 3;
+        for n in [u64::MAX, u64::MAX - 1, 1 << 63, (1 << 63) + 1] {
+            let k = 1000;
+            let iter = Hashes::new(hash1, hash2, n, k);
+            for (i, hash) in iter.enumerate() {
+                assert_eq!(hash, exact(i as u64, hash1, hash2, n));
+            }
+        }
+    }
+
+    #[test]
+    fn hashes_closed_form() {
+        fn check<T: Number + PartialEq>(hash1: T, hash2: T, n: T, k: u64) {
+            let iter = Hashes::new_u64(hash1, hash2, n, k);
+            for (i, hash) in Hashes::new_u64(hash1, hash2, n, k).enumerate() {
+                assert_eq!(T::from_repr(iter.point_at(i as u64)), hash);
+            }
+        }
+
+        check(u8::MAX - 1, u8::MAX - 2, u8::MAX, 1000);
+        check(200u8, 100, 201, 1000);
+        check(u64::MAX - 1, u64::MAX - 3, u64::MAX, 1000);
+        check(u128::MAX - 1, u128::MAX - 3, u128::MAX, 1000);
+        check(u128::MAX / 3, u128::MAX / 5, (1 << 100) + 7, 1000);
+    }
+
+    #[test]
+    fn hashes_closed_form_last_index() {
+        // `(i^3-i)/6 (mod n)` is computed exactly as `((i^3-i) mod 6n) / 6`.
+        fn exact(i: u128, x: u64, y: u64, n: u64) -> u64 {
+            let m = 6 * n as u128;
+            let cubic = ((i % m).pow(3) % m + m - i % m) % m / 6;
+            ((x as u128 + i * y as u128 + cubic) % n as u128) as u64
+        }
+
+        for n in [1009, 1 << 20, 3 << 30] {
+            let (x, y) = (n / 3, n / 7);
+            let hashes = Hashes::new(x, y, n, 0);
+            for i in [u64::MAX - 2, u64::MAX - 1, u64::MAX] {
+                let (h, next) = (exact(i.into(), x, y, n), exact(i as u128 + 1, x, y, n));
+                assert_eq!(hashes.hash_at(i), h);
+                let delta = (next + n - h) % n;
+                assert_eq!(hashes.delta_at(i), delta);
+            }
+        }
+    }
+
+    #[test]
+    fn hashes_double_ended() {
+        let hash_builder = Xxh3Builder::new();
+        let hash1 = hash_builder.with_seed(1).hash_one("mykey");
+        let hash2 = hash_builder.with_seed(2).hash_one("mykey");
+        let (n, k) = (u64::MAX, 100);
+
+        let forward = Hashes::new(hash1, hash2, n, k).collect::<Vec<_>>();
+        let backward = Hashes::new(hash1, hash2, n, k).rev().collect::<Vec<_>>();
+        assert!(forward.iter().rev().eq(&backward));
+        assert_eq!(
+            Hashes::new(hash1, hash2, n, k).last(),
+            forward.last().copied()
+        );
+
+        // Front and back meet in the middle.
+        let mut iter = Hashes::new(hash1, hash2, n, k);
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+        while let Some(hash) = iter.next() {
+            front.push(hash);
+            if let Some(hash) = iter.next_back() {
+                back.push(hash);
+            }
+        }
+        front.extend(back.into_iter().rev());
+        assert_eq!(front, forward);
+    }
+
+    #[test]
+    fn mul_high() {
+        fn check<T: Number>(a: T, b: T, expected: T) {
+            assert_eq!(a.mul_high(b), expected);
+        }
+        for (a, b) in [
+            (0, 7),
+            (1, u64::MAX),
+            (u64::MAX, u64::MAX),
+            (3 << 62, 12345),
+        ] {
+            let expected = (a as u128 * b as u128) >> 64;
+            check(a, b, expected as u64);
+            check(
+                a as u32,
+                b as u32,
+                ((a as u32 as u64 * b as u32 as u64) >> 32) as u32,
+            );
+            check(a as u128, b as u128, 0);
+            check((a as u128) << 64, b as u128, expected);
+            check(u128::MAX, b as u128, b as u128 - (b > 0) as u128);
+
+            // The generic (shift-and-add) implementation.
+            #[cfg(feature = "ruint")]
+            check(
+                ruint::aliases::U64::from(a),
+                ruint::aliases::U64::from(b),
+                ruint::aliases::U64::from(expected),
+            );
+        }
+    }
+
+    #[test]
+    fn add_mod_exhaustive() {
+        for n in 1..=u8::MAX {
+            for a in 0..n {
+                for b in 0..n {
+                    let expected = (a as u16 + b as u16) % n as u16;
+                    assert_eq!(add_mod(a, b, n) as u16, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn barrett_reduction() {
+        for n in 1..=u8::MAX {
+            let m = u8::MAX / n;
+            for v in 0..=u8::MAX {
+                assert_eq!(barrett(v, n, m), v % n);
+            }
+        }
+
+        let hash_builder = Xxh3Builder::new();
+        for n in [
+            1,
+            3,
+            1000,
+            1 << 40,
+            u64::MAX / 3 + 1,
+            u64::MAX - 1,
+            u64::MAX,
+        ] {
+            for i in 0..1000u64 {
+                let v = hash_builder.hash_one(i);
+                assert_eq!(barrett(v, n, u64::MAX / n), v % n);
+                let (v, n) = ((v as u128) << 64 | i as u128, n as u128 * 12345);
+                assert_eq!(barrett(v, n, u128::MAX / n), v % n);
+            }
         }
     }
+
+    #[test]
+    fn hashes_nth() {
+        let hash_builder = Xxh3Builder::new();
+        let hash1 = hash_builder.with_seed(1).hash_one("mykey");
+        let hash2 = hash_builder.with_seed(2).hash_one("mykey");
+        let (n, k) = (u64::MAX, 1000);
+        let forward = Hashes::new(hash1, hash2, n, k).collect::<Vec<_>>();
+
+        // Skip ahead, then continue walking the sequence.
+        let mut iter = Hashes::new(hash1, hash2, n, k);
+        assert_eq!(iter.nth(1), Some(forward[1]));
+        assert_eq!(iter.nth(10), Some(forward[12]));
+        assert_eq!(iter.next(), Some(forward[13]));
+        assert_eq!(iter.nth(500), Some(forward[514]));
+        assert!(iter.by_ref().eq(forward[515..].iter().copied()));
+        assert_eq!(iter.nth(1), None);
+
+        let mut iter = Hashes::new(hash1, hash2, n, k);
+        assert_eq!(iter.nth(999), Some(forward[999]));
+        assert_eq!(iter.next(), None);
+
+        let mut iter = Hashes::new(hash1, hash2, n, k);
+        assert_eq!(iter.nth(usize::MAX), None);
+        assert_eq!(iter.next(), None);
+
+        // From the back.
+        let mut iter = Hashes::new(hash1, hash2, n, k);
+        assert_eq!(iter.nth_back(0), Some(forward[999]));
+        assert_eq!(iter.nth_back(10), Some(forward[988]));
+        assert_eq!(iter.next_back(), Some(forward[987]));
+        assert_eq!(iter.nth(5), Some(forward[5]));
+        assert_eq!(iter.nth_back(980), Some(forward[6]));
+        assert_eq!(iter.next(), None);
+    }
 }
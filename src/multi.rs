@@ -0,0 +1,102 @@
+//! Iterator advancing the hash sequences of several keys in lockstep.
+
+use {
+    crate::{add_mod, from_u64_mod, DoubleHashHasher, HashingMode, Number},
+    std::{array, hash, iter},
+};
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Returns an iterator over `count` steps of the hash sequences of all the
+    /// `keys`, advanced in lockstep.
+    ///
+    /// Each step yields one hash value per key, i.e. the `i`-th item holds the
+    /// `i`-th hash value of every key. Since the sequences are independent, the
+    /// CPU can advance them in parallel, which is useful for batch membership
+    /// checks.
+    pub fn multi_hashes<const LANES: usize, K: hash::Hash>(
+        &self,
+        keys: &[K; LANES],
+        count: u64,
+    ) -> MultiHashes<T, LANES> {
+        let lanes = keys.each_ref().map(|key| self.hashes(key, count));
+        let n = self.n.to_repr();
+        MultiHashes {
+            x: lanes.each_ref().map(|hashes| hashes.hash1().to_repr()),
+            y: lanes.each_ref().map(|hashes| hashes.first_delta()),
+            n,
+            k: count,
+            cnt: 0,
+            mode: self.hashing_mode,
+        }
+    }
+}
+
+/// Iterator over the hash sequences of `LANES` keys, yielding `[T; LANES]`
+/// per step.
+///
+/// Created by [`DoubleHashHasher::multi_hashes`].
+#[derive(Debug, Clone)]
+pub struct MultiHashes<T: Number, const LANES: usize> {
+    /// The last hash point generated, per lane.
+    x: [T::Repr; LANES],
+
+    /// The difference between the next and the last hash points, per lane.
+    y: [T::Repr; LANES],
+
+    /// The size of the hash table.
+    n: T::Repr,
+
+    /// The number of steps to generate.
+    k: u64,
+
+    /// The current number of steps generated.
+    cnt: u64,
+
+    /// The recurrence used to generate hash points.
+    mode: HashingMode,
+}
+
+impl<T, const LANES: usize> Iterator for MultiHashes<T, LANES>
+where
+    T: Number,
+{
+    type Item = [T; LANES];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cnt == self.k {
+            return None;
+        }
+
+        // Same recurrence as in `Hashes`, with the step shared by all lanes.
+        if self.cnt > 0 {
+            let step = from_u64_mod(self.cnt, self.n);
+            let enhanced = self.mode == HashingMode::Enhanced;
+            for (x, y) in self.x.iter_mut().zip(self.y.iter_mut()) {
+                *x = add_mod(*x, *y, self.n);
+                if enhanced {
+                    *y = add_mod(*y, step, self.n);
+                }
+            }
+        }
+        self.cnt += 1;
+        Some(array::from_fn(|lane| T::from_repr(self.x[lane])))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.k - self.cnt) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl<T, const LANES: usize> iter::FusedIterator for MultiHashes<T, LANES> where T: Number {}
+
+/// See the note on `ExactSizeIterator` for [`Hashes`](crate::Hashes).
+#[cfg(not(any(target_pointer_width = "16", target_pointer_width = "32")))]
+impl<T, const LANES: usize> ExactSizeIterator for MultiHashes<T, LANES> where T: Number {}
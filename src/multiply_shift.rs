@@ -0,0 +1,128 @@
+//! Multiply-shift hashing, a strongly universal alternative to XXH3.
+
+use {
+    crate::{splitmix64, SeedableBuildHasher},
+    std::hash,
+};
+
+/// Builder of [`MultiplyShiftHasher`]s.
+///
+/// Can be used in place of the default XXH3 hash builders, see
+/// [`DoubleHashHasher::with_hash_builders`](crate::DoubleHashHasher::with_hash_builders):
+///
+/// ```
+/// use hash_iter::{DoubleHashHasher, HashIterHasher, MultiplyShiftBuilder};
+///
+/// let hasher = DoubleHashHasher::with_hash_builders(
+///     MultiplyShiftBuilder::new().with_seed(1),
+///     MultiplyShiftBuilder::new().with_seed(2),
+///     1000u64,
+/// );
+/// let hashes = hasher.hash_iter(&42u64, 3).collect::<Vec<_>>();
+/// assert!(hashes.iter().all(|&h| h < 1000));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MultiplyShiftBuilder {
+    seed: u64,
+}
+
+impl MultiplyShiftBuilder {
+    /// Constructs a new builder, with the default seed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the seed, out of which the (pseudo)random multipliers are derived.
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl hash::BuildHasher for MultiplyShiftBuilder {
+    type Hasher = MultiplyShiftHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        MultiplyShiftHasher {
+            seed: self.seed,
+            acc: coefficient(self.seed, 0),
+            words: 0,
+        }
+    }
+}
+
+impl SeedableBuildHasher for MultiplyShiftBuilder {
+    fn with_seed(self, seed: u64) -> Self {
+        MultiplyShiftBuilder::with_seed(self, seed)
+    }
+}
+
+/// Vector multiply-shift hasher (Dietzfelbinger, Thorup).
+///
+/// The key is consumed as a sequence of 64-bit words `x_1, ..., x_d`, and its
+/// hash is the high half of `b + a_1 * x_1 + ... + a_d * x_d`, computed
+/// modulo `2^128`, where `b` and `a_i` are 128-bit coefficients derived from
+/// the seed. For keys hashed as the same number of words (e.g. 32 or 64-bit
+/// integers), this family is strongly universal (2-independent) over the
+/// choice of coefficients.
+///
+/// Integers up to 64 bits are written as single words. Byte slices are split
+/// into 64-bit little-endian words, with the last word zero-padded (the
+/// standard [`Hash`](hash::Hash) implementations of slices and strings also
+/// write their length, or a terminator, so that keys do not collide because
+/// of the padding).
+#[derive(Debug, Clone)]
+pub struct MultiplyShiftHasher {
+    seed: u64,
+    acc: u128,
+    words: u64,
+}
+
+impl MultiplyShiftHasher {
+    /// Adds the next 64-bit word of the key.
+    #[inline]
+    fn write_word(&mut self, word: u64) {
+        self.words += 1;
+        let a = coefficient(self.seed, self.words);
+        self.acc = self.acc.wrapping_add(a.wrapping_mul(word as u128));
+    }
+}
+
+impl hash::Hasher for MultiplyShiftHasher {
+    fn finish(&self) -> u64 {
+        (self.acc >> 64) as u64
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.write_word(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.write_word(i.into());
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write_word(i.into());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write_word(i.into());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write_word(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_word(i as u64);
+    }
+}
+
+/// Returns the `i`-th 128-bit coefficient for the given seed (the `0`-th one
+/// is the additive term `b`).
+fn coefficient(seed: u64, i: u64) -> u128 {
+    ((splitmix64(seed, 2 * i) as u128) << 64) | splitmix64(seed, 2 * i + 1) as u128
+}
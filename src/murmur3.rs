@@ -0,0 +1,127 @@
+//! MurmurHash3 hash builders, for compatibility with other ecosystems.
+
+use {
+    crate::{sealed, DoubleHashHasher},
+    std::hash,
+};
+
+/// MurmurHash3 variant, and which part of its digest is the hash value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Murmur3Variant {
+    /// `MurmurHash3_x86_32`, zero-extended to 64 bits.
+    X86_32,
+
+    /// The low 64 bits of `MurmurHash3_x64_128`, i.e. its first 8 digest
+    /// bytes read as a little-endian integer (what Guava's `asLong()` returns).
+    #[default]
+    X64_128Low,
+
+    /// The high 64 bits of `MurmurHash3_x64_128`, i.e. its last 8 digest
+    /// bytes read as a little-endian integer.
+    X64_128High,
+}
+
+/// Builder of seeded [`Murmur3Hasher`]s.
+///
+/// Available with the `murmur3` feature. See
+/// [`DoubleHashHasher::with_murmur3`] for a ready-made double hasher.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Murmur3Builder {
+    seed: u32,
+    variant: Murmur3Variant,
+}
+
+impl Murmur3Builder {
+    /// Constructs a new builder, with zero seed (and the low half of
+    /// `MurmurHash3_x64_128`).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the seed.
+    pub fn with_seed(self, seed: u32) -> Self {
+        Self { seed, ..self }
+    }
+
+    /// Sets the variant.
+    pub fn with_variant(self, variant: Murmur3Variant) -> Self {
+        Self { variant, ..self }
+    }
+}
+
+impl hash::BuildHasher for Murmur3Builder {
+    type Hasher = Murmur3Hasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Murmur3Hasher {
+            seed: self.seed,
+            variant: self.variant,
+            bytes: Vec::new(),
+        }
+    }
+}
+
+/// MurmurHash3 hasher, see [`Murmur3Builder`].
+///
+/// MurmurHash3 is not incremental, so the written bytes are buffered, and
+/// hashed at once when finishing. Note that, to obtain the very same hash
+/// values as other implementations, the keys must be written as the same
+/// bytes, see [`RawBytes`](crate::RawBytes).
+#[derive(Debug, Clone)]
+pub struct Murmur3Hasher {
+    seed: u32,
+    variant: Murmur3Variant,
+    bytes: Vec<u8>,
+}
+
+impl hash::Hasher for Murmur3Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        const INFALLIBLE: &str = "reading from a slice cannot fail";
+        let mut bytes = &self.bytes[..];
+        match self.variant {
+            Murmur3Variant::X86_32 => {
+                ::murmur3::murmur3_32(&mut bytes, self.seed).expect(INFALLIBLE) as u64
+            }
+            Murmur3Variant::X64_128Low => {
+                ::murmur3::murmur3_x64_128(&mut bytes, self.seed).expect(INFALLIBLE) as u64
+            }
+            Murmur3Variant::X64_128High => {
+                (::murmur3::murmur3_x64_128(&mut bytes, self.seed).expect(INFALLIBLE) >> 64) as u64
+            }
+        }
+    }
+}
+
+impl<T> DoubleHashHasher<T, Murmur3Builder> {
+    /// Constructs a double hasher, whose starting hash points are the low and
+    /// the high halves of `MurmurHash3_x64_128`, as in Guava's Bloom filters.
+    ///
+    /// Other variants can be configured with
+    /// [`with_hash_builders`](Self::with_hash_builders):
+    ///
+    /// ```
+    /// use hash_iter::{DoubleHashHasher, HashIterHasher, Murmur3Builder, Murmur3Variant, RawBytes};
+    ///
+    /// let hasher = DoubleHashHasher::with_murmur3(0, 1000u64);
+    /// assert!(hasher.hash_iter(&RawBytes("hello"), 3).all(|h| h < 1000));
+    ///
+    /// let builder = Murmur3Builder::new().with_variant(Murmur3Variant::X86_32);
+    /// let hasher = DoubleHashHasher::with_hash_builders(builder, builder.with_seed(1), 1000u32);
+    /// assert!(hasher.hash_iter(&RawBytes("hello"), 3).all(|h| h < 1000));
+    /// ```
+    pub fn with_murmur3(seed: u32, n: T) -> Self
+    where
+        T: sealed::Modulus,
+    {
+        let builder = Murmur3Builder::new().with_seed(seed);
+        Self::with_hash_builders(
+            builder.with_variant(Murmur3Variant::X64_128Low),
+            builder.with_variant(Murmur3Variant::X64_128High),
+            n,
+        )
+    }
+}
@@ -0,0 +1,54 @@
+//! Two-level `(bucket, slot)` hash sequences.
+
+use {
+    crate::{DoubleHashHasher, Number},
+    num_traits::{Bounded, Zero},
+    std::hash,
+};
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Returns an iterator over `count` pairs of `(bucket, slot)` for the given
+    /// key, with buckets in `[0, n_buckets)` and slots in `[0, n_slots)`.
+    ///
+    /// The hasher's `n` is ignored: the sequence is computed modulo
+    /// `n_buckets * n_slots`, and each hash value is split into a quotient
+    /// (the bucket) and a remainder (the slot), so that both coordinates are
+    /// uniform.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either of the sizes is zero, or if their product overflows
+    /// the output type.
+    pub fn hash_iter_pairs<K>(
+        &self,
+        key: &K,
+        count: u64,
+        n_buckets: T,
+        n_slots: T,
+    ) -> impl Iterator<Item = (T, T)>
+    where
+        K: hash::Hash + ?Sized,
+    {
+        let (buckets, slots) = (n_buckets.to_repr(), n_slots.to_repr());
+        assert!(
+            !buckets.is_zero() && !slots.is_zero(),
+            "sizes must be non-zero"
+        );
+        assert!(
+            buckets <= T::Repr::max_value() / slots,
+            "too many slots in total"
+        );
+        let n = buckets * slots;
+
+        let (x, y) = self.base_hashes(key);
+        self.sequence::<T>(x, y, n, count).map(move |hash| {
+            let hash = hash.to_repr();
+            (T::from_repr(hash / slots), T::from_repr(hash % slots))
+        })
+    }
+}
@@ -0,0 +1,42 @@
+//! Parallel batch hashing, available with the `rayon` feature.
+
+use {
+    crate::{DoubleHashHasher, HashIterHasher, Number},
+    rayon::prelude::*,
+    std::hash,
+};
+
+/// Number of keys hashed per task (a multiple of the lane count of
+/// [`hash_iter_batch`](HashIterHasher::hash_iter_batch)).
+const BLOCK: usize = 256;
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number + Send + Sync,
+    H1: hash::BuildHasher + Sync,
+    H2: hash::BuildHasher + Sync,
+{
+    /// Same as [`hash_iter_batch`](HashIterHasher::hash_iter_batch), but
+    /// hashes blocks of keys in parallel, on the rayon thread pool (each block
+    /// being hashed by `hash_iter_batch`, vectorized where supported).
+    ///
+    /// The output is exactly the same as with the sequential version,
+    /// regardless of the number of threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out.len()` is not equal to `keys.len() * count`.
+    pub fn par_hash_iter_batch<K>(&self, keys: &[K], count: usize, out: &mut [T])
+    where
+        K: hash::Hash + Sync,
+    {
+        let len = keys.len().checked_mul(count);
+        assert_eq!(len, Some(out.len()), "out must hold count hashes per key");
+        if count == 0 {
+            return;
+        }
+        out.par_chunks_mut(BLOCK.saturating_mul(count))
+            .zip(keys.par_chunks(BLOCK))
+            .for_each(|(out, keys)| self.hash_iter_batch(keys, count, out));
+    }
+}
@@ -0,0 +1,65 @@
+//! Partitioned hash sequences, for partitioned Bloom filters.
+
+use {
+    crate::{rem, DoubleHashHasher, Number},
+    num_traits::{Bounded, FromPrimitive, One, ToPrimitive, WrappingAdd, WrappingSub, Zero},
+    std::hash,
+};
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Returns an iterator over `k` hash values for the given key, where the
+    /// `i`-th value falls into its own segment `[i * n / k, (i + 1) * n / k)`.
+    ///
+    /// Each value of the regular sequence is reduced modulo the length of its
+    /// segment, and offset by the segment start. When `n` is a multiple of
+    /// `k`, all segments have the same length, and the reduction introduces
+    /// no bias. As segments do not overlap, the values are always distinct.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is zero, or exceeds `n` (`2^BITS` for the full range).
+    pub fn partitioned_hashes<K>(&self, key: &K, k: u64) -> impl Iterator<Item = T>
+    where
+        K: hash::Hash + ?Sized,
+    {
+        let n = self.n.to_repr();
+        let parts = T::Repr::from_u64(k)
+            .filter(|parts| k > 0 && (*parts <= n || n.is_zero()))
+            .expect("k must be in [1, n]");
+
+        // Segment bounds are `i * q + i * r / k`, which avoids overflowing
+        // `i * n`. As `r < k`, the second term is computed in `u128`.
+        let (q, r) = if n.is_zero() {
+            // `2^BITS = max + 1`. For `k = 1`, `q` wraps to `0`, but then only
+            // the bounds of the whole range are ever used.
+            let (max_q, max_r) = (T::Repr::max_value() / parts, T::Repr::max_value() % parts);
+            let carry = max_r + T::Repr::one();
+            (max_q.wrapping_add(&(carry / parts)), carry % parts)
+        } else {
+            (n / parts, n % parts)
+        };
+        let r = r.to_u64().expect("invalid remainder");
+        let bound = move |i: u64| {
+            // The end of the last segment is `n`, which may be `2^BITS`.
+            if i == k {
+                return n;
+            }
+            let carry = (i as u128 * r as u128 / k as u128) as u64;
+            T::Repr::from_u64(i).expect("invalid index") * q
+                + T::Repr::from_u64(carry).expect("invalid carry")
+        };
+
+        let (x, y) = self.base_hashes(key);
+        self.sequence::<T>(x, y, n, k)
+            .zip(0..)
+            .map(move |(hash, i)| {
+                let (start, end) = (bound(i), bound(i + 1));
+                T::from_repr(start + rem(hash.to_repr(), end.wrapping_sub(&start)))
+            })
+    }
+}
@@ -0,0 +1,265 @@
+//! Key encoding independent of [`std::hash::Hash`], whose output is not
+//! guaranteed to be stable across platforms or compiler versions.
+
+use {
+    crate::{incremental::DoubleHashStreamHasher, DoubleHashHasher, Number},
+    std::hash,
+};
+
+/// Key with a byte-exact encoding, stable across platforms and compiler
+/// versions (unlike [`Hash`](hash::Hash)), so that filters can be persisted.
+///
+/// Implementations encode:
+/// - integers, as fixed-width little-endian bytes (`usize` and `isize` as 64
+///   bits), `bool` as one byte, `char` as a `u32`, floats as their bits;
+/// - strings and slices (of any length), as their length (a `u64`) followed by
+///   their elements;
+/// - arrays, tuples and references, as their elements, in order;
+/// - options, as a `0` byte, or a `1` byte followed by the value.
+///
+/// ```
+/// use hash_iter::{DoubleHashHasher, PortableKey};
+///
+/// struct User {
+///     id: u64,
+///     name: String,
+/// }
+///
+/// impl PortableKey for User {
+///     fn encode<S: hash_iter::KeySink + ?Sized>(&self, sink: &mut S) {
+///         self.id.encode(sink);
+///         self.name.encode(sink);
+///     }
+/// }
+///
+/// let user = User {
+///     id: 42,
+///     name: "alice".to_string(),
+/// };
+/// let hasher = DoubleHashHasher::new();
+/// assert!(hasher
+///     .hash_iter_portable(&user, 3)
+///     .eq(hasher.hash_iter_portable(&(42u64, "alice"), 3)));
+/// ```
+pub trait PortableKey {
+    /// Writes the encoding of the key into the `sink`.
+    fn encode<S: KeySink + ?Sized>(&self, sink: &mut S);
+
+    /// Writes the encoding of the elements of a slice, without its length.
+    ///
+    /// Overridden by `u8`, so that byte slices are written at once.
+    fn encode_slice<S: KeySink + ?Sized>(data: &[Self], sink: &mut S)
+    where
+        Self: Sized,
+    {
+        for item in data {
+            item.encode(sink);
+        }
+    }
+}
+
+/// Receiver of the bytes of [`PortableKey`] encodings.
+pub trait KeySink {
+    /// Writes the next bytes of the encoding.
+    fn write(&mut self, bytes: &[u8]);
+}
+
+impl KeySink for Vec<u8> {
+    fn write(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}
+
+impl<T, H1, H2> KeySink for DoubleHashStreamHasher<'_, T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+    H1::Hasher: Clone,
+    H2::Hasher: Clone,
+{
+    fn write(&mut self, bytes: &[u8]) {
+        DoubleHashStreamHasher::write(self, bytes);
+    }
+}
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+    H1::Hasher: Clone,
+    H2::Hasher: Clone,
+{
+    /// Returns an iterator over `count` hash values of the key, hashing its
+    /// [`PortableKey`] encoding instead of its [`Hash`](hash::Hash)
+    /// implementation.
+    ///
+    /// The emitted sequence is then as stable as the hash functions are: e.g.
+    /// with the default XXH3 backend and output types of up to 64 bits, it is
+    /// the same on every platform.
+    pub fn hash_iter_portable<K>(&self, key: &K, count: usize) -> impl Iterator<Item = T>
+    where
+        K: PortableKey + ?Sized,
+    {
+        let mut stream = self.stream_hasher();
+        key.encode(&mut stream);
+        stream.finish_iter(count)
+    }
+}
+
+macro_rules! impl_int {
+    ($($ty:ty),*) => {$(
+        impl PortableKey for $ty {
+            #[inline]
+            fn encode<S: KeySink + ?Sized>(&self, sink: &mut S) {
+                sink.write(&self.to_le_bytes());
+            }
+        }
+    )*};
+}
+
+impl_int!(u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl PortableKey for u8 {
+    #[inline]
+    fn encode<S: KeySink + ?Sized>(&self, sink: &mut S) {
+        sink.write(&[*self]);
+    }
+
+    #[inline]
+    fn encode_slice<S: KeySink + ?Sized>(data: &[Self], sink: &mut S) {
+        sink.write(data);
+    }
+}
+
+impl PortableKey for usize {
+    #[inline]
+    fn encode<S: KeySink + ?Sized>(&self, sink: &mut S) {
+        (*self as u64).encode(sink);
+    }
+}
+
+impl PortableKey for isize {
+    #[inline]
+    fn encode<S: KeySink + ?Sized>(&self, sink: &mut S) {
+        (*self as i64).encode(sink);
+    }
+}
+
+impl PortableKey for bool {
+    #[inline]
+    fn encode<S: KeySink + ?Sized>(&self, sink: &mut S) {
+        u8::from(*self).encode(sink);
+    }
+}
+
+impl PortableKey for char {
+    #[inline]
+    fn encode<S: KeySink + ?Sized>(&self, sink: &mut S) {
+        u32::from(*self).encode(sink);
+    }
+}
+
+impl PortableKey for f32 {
+    #[inline]
+    fn encode<S: KeySink + ?Sized>(&self, sink: &mut S) {
+        self.to_bits().encode(sink);
+    }
+}
+
+impl PortableKey for f64 {
+    #[inline]
+    fn encode<S: KeySink + ?Sized>(&self, sink: &mut S) {
+        self.to_bits().encode(sink);
+    }
+}
+
+impl PortableKey for str {
+    #[inline]
+    fn encode<S: KeySink + ?Sized>(&self, sink: &mut S) {
+        self.as_bytes().encode(sink);
+    }
+}
+
+impl PortableKey for String {
+    #[inline]
+    fn encode<S: KeySink + ?Sized>(&self, sink: &mut S) {
+        self.as_str().encode(sink);
+    }
+}
+
+impl<T: PortableKey> PortableKey for [T] {
+    #[inline]
+    fn encode<S: KeySink + ?Sized>(&self, sink: &mut S) {
+        self.len().encode(sink);
+        T::encode_slice(self, sink);
+    }
+}
+
+impl<T: PortableKey, const N: usize> PortableKey for [T; N] {
+    #[inline]
+    fn encode<S: KeySink + ?Sized>(&self, sink: &mut S) {
+        T::encode_slice(self, sink);
+    }
+}
+
+impl<T: PortableKey> PortableKey for Vec<T> {
+    #[inline]
+    fn encode<S: KeySink + ?Sized>(&self, sink: &mut S) {
+        self.as_slice().encode(sink);
+    }
+}
+
+impl<T: PortableKey> PortableKey for Option<T> {
+    #[inline]
+    fn encode<S: KeySink + ?Sized>(&self, sink: &mut S) {
+        match self {
+            None => 0u8.encode(sink),
+            Some(value) => {
+                1u8.encode(sink);
+                value.encode(sink);
+            }
+        }
+    }
+}
+
+impl<T: PortableKey + ?Sized> PortableKey for &T {
+    #[inline]
+    fn encode<S: KeySink + ?Sized>(&self, sink: &mut S) {
+        (**self).encode(sink);
+    }
+}
+
+impl<T: PortableKey + ?Sized> PortableKey for Box<T> {
+    #[inline]
+    fn encode<S: KeySink + ?Sized>(&self, sink: &mut S) {
+        (**self).encode(sink);
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident)+) => {
+        impl<$($name: PortableKey),+> PortableKey for ($($name,)+) {
+            #[inline]
+            #[allow(non_snake_case)]
+            fn encode<S: KeySink + ?Sized>(&self, sink: &mut S) {
+                let ($($name,)+) = self;
+                $($name.encode(sink);)+
+            }
+        }
+    };
+}
+
+impl_tuple!(A);
+impl_tuple!(A B);
+impl_tuple!(A B C);
+impl_tuple!(A B C D);
+impl_tuple!(A B C D E);
+impl_tuple!(A B C D E F);
+impl_tuple!(A B C D E F G);
+impl_tuple!(A B C D E F G H);
+impl_tuple!(A B C D E F G H I);
+impl_tuple!(A B C D E F G H I J);
+impl_tuple!(A B C D E F G H I J K);
+impl_tuple!(A B C D E F G H I J K L);
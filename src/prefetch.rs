@@ -0,0 +1,50 @@
+//! Prefetching of the probed slots, to hide memory latency.
+
+use {
+    crate::{DoubleHashHasher, Hashes, Number},
+    std::hash,
+};
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Issues prefetch hints for the `count` slots of `table` probed by the
+    /// given key, and returns the iterator over their indices.
+    ///
+    /// Since all the indices are known up front, the cache misses of the
+    /// probes overlap, instead of being paid one after another. Indices out of
+    /// the bounds of `table` are not prefetched.
+    ///
+    /// Prefetching is only a hint: on targets without prefetch support, this
+    /// is the same as [`hashes`](Self::hashes).
+    pub fn prefetch_hashes<K, E>(&self, key: &K, count: u64, table: &[E]) -> Hashes<T>
+    where
+        K: hash::Hash + ?Sized,
+    {
+        let hashes = self.hashes(key, count);
+        for idx in hashes.clone() {
+            if let Some(slot) = idx.to_usize().and_then(|idx| table.get(idx)) {
+                prefetch(slot);
+            }
+        }
+        hashes
+    }
+}
+
+/// Hints the CPU to fetch the cache line holding `slot` into all levels of the
+/// cache.
+#[inline(always)]
+fn prefetch<E>(slot: &E) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        use std::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+        // SAFETY: prefetching has no observable effects, and the pointer is
+        // derived from a valid reference anyway.
+        unsafe { _mm_prefetch::<_MM_HINT_T0>((slot as *const E).cast()) };
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = slot;
+}
@@ -0,0 +1,94 @@
+//! Probe sequence for open-addressing tables with power-of-two capacity.
+
+use {
+    crate::{odd, DoubleHashHasher, Hashes, HashingMode},
+    std::{hash, iter},
+};
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Returns the probe sequence of the given key in an open-addressing table
+    /// of `capacity` slots, probed in groups of `group_width` slots.
+    ///
+    /// The sequence is driven by the two start points of the key (see
+    /// [`ProbeSequence`] for details).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` or `group_width` is not a power of two, or if
+    /// `group_width` exceeds `capacity`.
+    pub fn probe_sequence<K: hash::Hash + ?Sized>(
+        &self,
+        key: &K,
+        capacity: usize,
+        group_width: usize,
+    ) -> ProbeSequence {
+        let words = self.key_words(key);
+        ProbeSequence::new(words.word1(0), words.word2(0), capacity, group_width)
+    }
+}
+
+/// Iterator over the positions probed for a key in an open-addressing table
+/// with power-of-two capacity (hashbrown-style group probing).
+///
+/// Each position is the start of a group of `group_width` consecutive slots
+/// (wrapping around the end of the table). Positions follow the double hashing
+/// sequence of the key, `h(i) = h1 + i * h2 * group_width (mod capacity)`,
+/// with `h2` made odd: as the step is then coprime with the number of groups,
+/// this visits every group exactly once, i.e. the sequence ends after
+/// `capacity / group_width` positions, once all the slots have been probed.
+///
+/// The cubic term of enhanced double hashing is left out, as with power-of-two
+/// moduli it makes sequences revisit some groups while skipping others (see
+/// [`HashingMode::Plain`]).
+#[derive(Debug, Clone)]
+pub struct ProbeSequence {
+    /// The sequence of the first slots of the groups.
+    hashes: Hashes<u64>,
+}
+
+impl ProbeSequence {
+    /// Constructs the probe sequence starting at `hash1` (modulo `capacity`),
+    /// and stepping by groups according to `hash2`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` or `group_width` is not a power of two, or if
+    /// `group_width` exceeds `capacity`.
+    pub fn new(hash1: u64, hash2: u64, capacity: usize, group_width: usize) -> Self {
+        assert!(
+            capacity.is_power_of_two(),
+            "capacity must be a power of two"
+        );
+        assert!(
+            group_width.is_power_of_two() && group_width <= capacity,
+            "group_width must be a power of two, not exceeding capacity"
+        );
+        let (n, groups) = (capacity as u64, (capacity / group_width) as u64);
+        let mask = n - 1;
+        let step = odd(hash2).wrapping_mul(group_width as u64) & mask;
+        Self {
+            hashes: Hashes::from_repr(hash1 & mask, step, n, groups)
+                .with_hashing_mode(HashingMode::Plain),
+        }
+    }
+}
+
+impl Iterator for ProbeSequence {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.hashes.next().map(|pos| pos as usize)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.hashes.size_hint()
+    }
+}
+
+impl iter::FusedIterator for ProbeSequence {}
+
+impl ExactSizeIterator for ProbeSequence {}
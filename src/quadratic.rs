@@ -0,0 +1,140 @@
+//! Quadratic probing sequences.
+
+use {
+    crate::{add_mod, rem, DoubleHashHasher, Number},
+    num_traits::One,
+    std::{hash, iter},
+};
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Returns an iterator over `count` hash values for the given key, using
+    /// quadratic probing: `h(i) = h1 + c1 * i + c2 * i^2 (mod n)`.
+    ///
+    /// Only the first hash of the key is used.
+    pub fn quadratic_hashes<K>(&self, key: &K, count: u64, c1: T, c2: T) -> QuadraticHashes<T>
+    where
+        K: hash::Hash + ?Sized,
+    {
+        QuadraticHashes::new(self.hashes(key, 0).hash1(), c1, c2, self.n, count)
+    }
+
+    /// Returns an iterator over `count` hash values for the given key, using
+    /// triangular probing: `h(i) = h1 + i * (i+1)/2 (mod n)`.
+    ///
+    /// Only the first hash of the key is used.
+    pub fn triangular_hashes<K>(&self, key: &K, count: u64) -> QuadraticHashes<T>
+    where
+        K: hash::Hash + ?Sized,
+    {
+        QuadraticHashes::triangular(self.hashes(key, 0).hash1(), self.n, count)
+    }
+}
+
+/// Iterator over hash values generated using quadratic probing.
+///
+/// The sequence is computed using forward differencing, so that no
+/// multiplications are needed, and intermediate values never overflow.
+#[derive(Debug, Clone)]
+pub struct QuadraticHashes<T: Number> {
+    /// The size of the hash table.
+    n: T::Repr,
+
+    /// The number of hash points to generate.
+    k: u64,
+
+    /// The current number of hash points generated.
+    cnt: u64,
+
+    /// The next hash point.
+    x: T::Repr,
+
+    /// The difference between the hash point after the next one and the next
+    /// one.
+    d: T::Repr,
+
+    /// The (constant) second-order difference.
+    dd: T::Repr,
+}
+
+impl<T> QuadraticHashes<T>
+where
+    T: Number,
+{
+    /// Constructs a new quadratic probing iterator: `h(i) = h1 + c1 * i + c2 *
+    /// i^2 (mod n)`, for `i` in `[0, k)`.
+    ///
+    /// As with [`Hashes`](crate::Hashes), `n = 0` stands for the whole range
+    /// of the output type, i.e. the computations simply wrap.
+    pub fn new(h1: T, c1: T, c2: T, n: T, k: u64) -> Self {
+        let n = n.to_repr();
+        let (c1, c2) = (rem(c1.to_repr(), n), rem(c2.to_repr(), n));
+        // `h(i+1) - h(i) = c1 + c2 * (2i + 1)`, which grows by `2 * c2`.
+        Self::from_differences(
+            rem(h1.to_repr(), n),
+            add_mod(c1, c2, n),
+            add_mod(c2, c2, n),
+            n,
+            k,
+        )
+    }
+
+    /// Constructs a new triangular probing iterator: `h(i) = h1 + i * (i+1)/2
+    /// (mod n)`, for `i` in `[0, k)`.
+    ///
+    /// When `n` is a power of two, the first `n` hash points visit every slot
+    /// exactly once (this includes the whole range, `n = 0`, of the output
+    /// type).
+    pub fn triangular(h1: T, n: T, k: u64) -> Self {
+        let n = n.to_repr();
+        let one = rem(T::Repr::one(), n);
+        Self::from_differences(rem(h1.to_repr(), n), one, one, n, k)
+    }
+
+    fn from_differences(x: T::Repr, d: T::Repr, dd: T::Repr, n: T::Repr, k: u64) -> Self {
+        Self {
+            n,
+            k,
+            cnt: 0,
+            x,
+            d,
+            dd,
+        }
+    }
+}
+
+impl<T> Iterator for QuadraticHashes<T>
+where
+    T: Number,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cnt == self.k {
+            return None;
+        }
+
+        let x = self.x;
+        self.x = add_mod(self.x, self.d, self.n);
+        self.d = add_mod(self.d, self.dd, self.n);
+        self.cnt += 1;
+        Some(T::from_repr(x))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.k - self.cnt) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl<T> iter::FusedIterator for QuadraticHashes<T> where T: Number {}
+
+/// See the note on `ExactSizeIterator` for [`Hashes`](crate::Hashes).
+#[cfg(not(any(target_pointer_width = "16", target_pointer_width = "32")))]
+impl<T> ExactSizeIterator for QuadraticHashes<T> where T: Number {}
@@ -0,0 +1,68 @@
+//! Randomly seeded builders, available with the `rand` feature.
+
+use {
+    crate::{DoubleHashBuilder, Number},
+    rand_core::RngCore,
+    std::sync::OnceLock,
+};
+
+/// Returns a pair of random 128-bit keys, drawn from the operating system
+/// once per process.
+///
+/// # Panics
+///
+/// Panics if the operating system fails to provide random bytes.
+#[cfg(feature = "siphash")]
+pub(crate) fn process_keys() -> [u128; 2] {
+    static KEYS: OnceLock<[u128; 2]> = OnceLock::new();
+    *KEYS.get_or_init(|| {
+        let mut bytes = [0; 32];
+        getrandom::fill(&mut bytes).expect("failed to get random keys");
+        let (key1, key2) = bytes.split_at(16);
+        [key1, key2].map(|key| u128::from_le_bytes(key.try_into().unwrap()))
+    })
+}
+
+impl<T: Number> DoubleHashBuilder<T> {
+    /// Constructs a new hash iterator builder, seeded with a random master
+    /// seed (see [`with_seed`](Self::with_seed)).
+    ///
+    /// Like with `std`'s `RandomState`, the seed is drawn from the operating
+    /// system once per process, so that all the builders of the process agree
+    /// with each other, while sequences differ from run to run (so they must
+    /// not be persisted).
+    ///
+    /// This does not protect against HashDoS: XXH3 is not designed to resist
+    /// adversaries, who may craft colliding keys whatever the seed. For keys
+    /// chosen by adversaries, use keyed SipHash instead, e.g.
+    /// `DoubleHashHasher::secure(n)` (with the `siphash` feature).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system fails to provide random bytes.
+    pub fn random() -> Self {
+        static MASTER: OnceLock<u64> = OnceLock::new();
+        let master = MASTER.get_or_init(|| getrandom::u64().expect("failed to get a random seed"));
+        Self::new().with_seed(*master)
+    }
+
+    /// Constructs a new hash iterator builder, with both seeds drawn from the
+    /// given random number generator.
+    ///
+    /// Makes randomized setups reproducible, e.g. with a seeded `StdRng` in
+    /// tests, and `OsRng` in production.
+    pub fn from_rng(rng: &mut impl RngCore) -> Self {
+        let seed1 = rng.next_u64();
+        let seed2 = rng.next_u64();
+        Self::new().with_u64_seeds(seed1, seed2)
+    }
+
+    /// Sets a custom XXH3 secret (see [`with_secret`](Self::with_secret)),
+    /// drawn from the given random number generator, e.g. to rotate the
+    /// sequences of all the keys, whatever their length.
+    pub fn with_secret_from_rng(self, rng: &mut impl RngCore) -> Self {
+        let mut secret = [0; 192];
+        rng.fill_bytes(&mut secret);
+        self.with_secret(&secret)
+    }
+}
@@ -0,0 +1,72 @@
+//! Rapidhash hash builders.
+
+use {
+    crate::{sealed, splitmix64, DoubleHashHasher, SeedableBuildHasher},
+    ::rapidhash::quality::RapidHasher,
+    std::hash,
+};
+
+/// Builder of seeded [rapidhash](https://crates.io/crates/rapidhash) hashers
+/// (the `quality` variant, with the default secrets).
+///
+/// Unlike `rapidhash`'s own seedable states, the secrets are not randomized
+/// at startup, so that the same seed produces the same hash values across
+/// program runs. Available with the `rapidhash` feature. See
+/// [`DoubleHashHasher::with_rapidhash`] for a ready-made double hasher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RapidHashBuilder {
+    seed: u64,
+}
+
+impl RapidHashBuilder {
+    /// Constructs a new builder, with the default seed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the seed.
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl Default for RapidHashBuilder {
+    fn default() -> Self {
+        Self {
+            seed: RapidHasher::DEFAULT_SEED,
+        }
+    }
+}
+
+impl hash::BuildHasher for RapidHashBuilder {
+    type Hasher = RapidHasher<'static>;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        RapidHasher::new(self.seed)
+    }
+}
+
+impl SeedableBuildHasher for RapidHashBuilder {
+    fn with_seed(self, seed: u64) -> Self {
+        RapidHashBuilder::with_seed(self, seed)
+    }
+}
+
+impl<T> DoubleHashHasher<T, RapidHashBuilder> {
+    /// Constructs a double hasher, whose starting hash points are computed
+    /// with rapidhash, seeded by two independent seeds derived from `seed`.
+    ///
+    /// ```
+    /// use hash_iter::{DoubleHashHasher, HashIterHasher};
+    ///
+    /// let hasher = DoubleHashHasher::with_rapidhash(42, 1000u64);
+    /// assert!(hasher.hash_iter(&"hello", 3).all(|h| h < 1000));
+    /// ```
+    pub fn with_rapidhash(seed: u64, n: T) -> Self
+    where
+        T: sealed::Modulus,
+    {
+        let builder = |i| RapidHashBuilder::new().with_seed(splitmix64(seed, i));
+        Self::with_hash_builders(builder(0), builder(1), n)
+    }
+}
@@ -0,0 +1,56 @@
+//! Hashing of keys read out of an [`io::Read`] source, available with the `io`
+//! feature.
+
+use {
+    crate::{DoubleHashHasher, Number},
+    std::{hash, io},
+};
+
+/// Size of the buffer the source is read through.
+const BUF_SIZE: usize = 8 * 1024;
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+    H1::Hasher: Clone,
+    H2::Hasher: Clone,
+{
+    /// Returns an iterator over `count` hash values of the key made of all the
+    /// bytes of `reader`, up to its end.
+    ///
+    /// The source is streamed through the hashers with a fixed-size buffer (see
+    /// [`stream_hasher`](Self::stream_hasher)), so large keys (e.g. whole
+    /// files) are never held in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of `reader`, other than
+    /// [`io::ErrorKind::Interrupted`] (after which reading is retried).
+    ///
+    /// ```
+    /// use hash_iter::DoubleHashHasher;
+    ///
+    /// let hasher = DoubleHashHasher::new();
+    /// let hashes = hasher.hash_iter_reader(&b"hello"[..], 3)?;
+    /// assert!(hashes.eq(hasher.hash_iter_bytes(b"hello", 3)));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn hash_iter_reader<R: io::Read>(
+        &self,
+        mut reader: R,
+        count: usize,
+    ) -> io::Result<impl Iterator<Item = T>> {
+        let mut stream = self.stream_hasher();
+        let mut buf = [0; BUF_SIZE];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => return Ok(stream.finish_iter(count)),
+                Ok(len) => stream.write(&buf[..len]),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
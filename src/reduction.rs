@@ -0,0 +1,137 @@
+//! Methods of mapping hash values into `[0, n)`.
+
+use crate::{is_power_of_two, Number};
+
+/// Maps (full-width) hash values into `[0, n)`.
+///
+/// Used to reduce the start points of the sequence: the rest of the sequence
+/// is computed using modular additions, which never divide. Besides the
+/// provided implementations, custom ones can be used with
+/// [`DoubleHashHasher::hashes_with_reduction`](crate::DoubleHashHasher::hashes_with_reduction).
+pub trait RangeReduction {
+    /// Maps `hash` into `[0, n)`, for `n > 0`.
+    fn reduce<R: Number>(&self, hash: R, n: R) -> R;
+}
+
+/// Remainder of the division by `n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Modulo;
+
+impl RangeReduction for Modulo {
+    #[inline]
+    fn reduce<R: Number>(&self, hash: R, n: R) -> R {
+        hash % n
+    }
+}
+
+/// Low bits of the hash value: `hash & (n - 1)`, for power-of-two `n`.
+///
+/// For other values of `n`, falls back to [`Modulo`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BitMask;
+
+impl RangeReduction for BitMask {
+    #[inline]
+    fn reduce<R: Number>(&self, hash: R, n: R) -> R {
+        if is_power_of_two(n) {
+            hash.low_bits(n)
+        } else {
+            hash % n
+        }
+    }
+}
+
+/// Multiply-high (Lemire's "fastrange") reduction: `floor(hash * n /
+/// 2^BITS)`, where `BITS` is the width of the representation type.
+///
+/// Avoids the division, and maps uniform hash values onto `[0, n)` as evenly
+/// as the modulo does (slots differ in probability by at most one part in
+/// `2^BITS / n`). Uses the high bits of the hash values, rather than the low
+/// ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Lemire;
+
+impl RangeReduction for Lemire {
+    #[inline]
+    fn reduce<R: Number>(&self, hash: R, n: R) -> R {
+        hash.mul_high(n)
+    }
+}
+
+/// No reduction: hash values are used as they are.
+///
+/// Meant for hash values that are already in range (e.g. when `n` covers the
+/// whole output type). The sequence still reduces start points that turn out
+/// to be out of range, but only then does it divide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Identity;
+
+impl RangeReduction for Identity {
+    #[inline]
+    fn reduce<R: Number>(&self, hash: R, _n: R) -> R {
+        hash
+    }
+}
+
+/// Range reduction used by a hasher, see the implementations of
+/// [`RangeReduction`] for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Reduction {
+    /// See [`Modulo`].
+    #[default]
+    Modulo,
+
+    /// See [`Lemire`].
+    Lemire,
+
+    /// See [`BitMask`].
+    ///
+    /// The second start point is also forced to be odd (see
+    /// [`DoubleHashBuilder::with_nonzero_h2`](crate::DoubleHashBuilder::with_nonzero_h2)),
+    /// so that the plain double hashing sequence visits all the slots, and
+    /// that no division is ever needed.
+    BitMask,
+
+    /// See [`Identity`].
+    Identity,
+
+    /// [`BitMask`](Self::BitMask) if `n` is a power of two,
+    /// [`Modulo`](Self::Modulo) otherwise.
+    Auto,
+}
+
+impl RangeReduction for Reduction {
+    #[inline]
+    fn reduce<R: Number>(&self, hash: R, n: R) -> R {
+        match self {
+            Self::Modulo => Modulo.reduce(hash, n),
+            Self::Lemire => Lemire.reduce(hash, n),
+            Self::BitMask | Self::Auto => BitMask.reduce(hash, n),
+            Self::Identity => Identity.reduce(hash, n),
+        }
+    }
+}
+
+impl From<Modulo> for Reduction {
+    fn from(_: Modulo) -> Self {
+        Self::Modulo
+    }
+}
+
+impl From<BitMask> for Reduction {
+    fn from(_: BitMask) -> Self {
+        Self::BitMask
+    }
+}
+
+impl From<Lemire> for Reduction {
+    fn from(_: Lemire) -> Self {
+        Self::Lemire
+    }
+}
+
+impl From<Identity> for Reduction {
+    fn from(_: Identity) -> Self {
+        Self::Identity
+    }
+}
@@ -0,0 +1,48 @@
+//! Window positions and coefficient patterns, for ribbon filters.
+
+use {
+    crate::{rem, DoubleHashHasher, Number},
+    num_traits::{FromPrimitive, One, WrappingAdd, WrappingSub, Zero},
+    std::hash,
+};
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Returns the ribbon row of the given key: the start of its window of
+    /// `width` consecutive slots, and the `width`-bit coefficient pattern over
+    /// that window.
+    ///
+    /// The start is the first hash value reduced into `[0, n - width]`, so
+    /// that the window always fits into the table. The coefficients are the
+    /// low `width` bits of the key's second hash word, with the lowest bit
+    /// always set (so that every row has a leading coefficient at its start).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is not in `[1, 64]`, or exceeds `n` (`2^BITS` for the
+    /// full range).
+    pub fn ribbon_row<K>(&self, key: &K, width: u32) -> (T, u64)
+    where
+        K: hash::Hash + ?Sized,
+    {
+        assert!((1..=64).contains(&width), "width must be in [1, 64]");
+        let n = self.n.to_repr();
+        let w = T::Repr::from_u32(width)
+            .filter(|w| *w <= n || n.is_zero())
+            .expect("width must not exceed n");
+
+        let mode = self.truncation_mode;
+        let words = self.key_words(key);
+        let x = T::Repr::from_hash_words(|i| mode.apply::<T::Repr>(words.word1(i)));
+        // `n - w + 1` positions, which wraps to `0` (the whole range) for the
+        // full range and `w = 1`.
+        let start = rem(x, n.wrapping_sub(&w).wrapping_add(&T::Repr::one()));
+        let mask = u64::MAX >> (64 - width);
+        let coefficients = words.word2(0) & mask | 1;
+        (T::from_repr(start), coefficients)
+    }
+}
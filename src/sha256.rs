@@ -0,0 +1,89 @@
+//! SHA-256 hash builders, for deployments restricted to approved primitives.
+
+use {
+    crate::{digest::digest_words, sealed, DigestHalf, DoubleHashHasher, KeyHashing},
+    sha2::{Digest, Sha256},
+    std::hash,
+};
+
+/// Builder of [`Sha256Hasher`]s.
+///
+/// Available with the `sha2` feature. See [`DoubleHashHasher::with_sha256`]
+/// for a ready-made double hasher.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Sha256Builder {
+    half: DigestHalf,
+}
+
+impl Sha256Builder {
+    /// Constructs a new builder, taking hash values out of the first half of
+    /// the digest.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the half of the digest the hash values are taken out of.
+    pub fn with_half(self, half: DigestHalf) -> Self {
+        Self { half }
+    }
+}
+
+impl hash::BuildHasher for Sha256Builder {
+    type Hasher = Sha256Hasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Sha256Hasher {
+            sha: Sha256::new(),
+            half: self.half,
+        }
+    }
+}
+
+/// SHA-256 hasher, see [`Sha256Builder`].
+///
+/// The hash value is the leading 8 bytes of the configured half of the
+/// digest, read as a big-endian integer.
+#[derive(Debug, Clone)]
+pub struct Sha256Hasher {
+    sha: Sha256,
+    half: DigestHalf,
+}
+
+impl hash::Hasher for Sha256Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.sha.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        self.half.word(&self.sha.clone().finalize().into())
+    }
+}
+
+impl Sha256Hasher {
+    /// Returns the words of both halves of the digest, see
+    /// [`KeyHashing::Sha256`].
+    pub(crate) fn digest_words(&self) -> u128 {
+        digest_words(&self.sha.clone().finalize().into())
+    }
+}
+
+impl<T> DoubleHashHasher<T, Sha256Builder> {
+    /// Constructs a double hasher, whose starting hash points are taken out of
+    /// the two halves of the SHA-256 digest of the key (computed once per key,
+    /// see [`KeyHashing::Sha256`]).
+    ///
+    /// ```
+    /// use hash_iter::{DoubleHashHasher, HashIterHasher};
+    ///
+    /// let hasher = DoubleHashHasher::with_sha256(1000u64);
+    /// assert!(hasher.hash_iter(&"hello", 3).all(|h| h < 1000));
+    /// ```
+    pub fn with_sha256(n: T) -> Self
+    where
+        T: sealed::Modulus,
+    {
+        let builder = Sha256Builder::new();
+        Self::with_hash_builders(builder, builder.with_half(DigestHalf::Second), n)
+            .with_key_hashing(KeyHashing::Sha256)
+    }
+}
@@ -0,0 +1,81 @@
+//! Lane-parallel batch hashing using portable SIMD, available with the
+//! `nightly` feature.
+
+use {
+    crate::{
+        from_u64_mod,
+        wide::{from_lane, rows, Isa, LANES},
+        DoubleHashHasher,
+        HashIterHasher,
+        Number,
+    },
+    std::{
+        hash,
+        simd::{cmp::SimdPartialOrd, u64x4, Select},
+    },
+};
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number<Repr = u64>,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Same as [`hash_iter_batch`](crate::HashIterHasher::hash_iter_batch),
+    /// but advances the sequences of several keys at once using portable SIMD,
+    /// instead of the instruction set detected at runtime (the keys are still
+    /// hashed one by one).
+    ///
+    /// Available for the output types computed in 64 bits (`u64`, `usize` and
+    /// `i64`), with the `nightly` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out.len()` is not equal to `keys.len() * count`.
+    pub fn simd_hash_iter_batch<K>(&self, keys: &[K], count: usize, out: &mut [T])
+    where
+        K: hash::Hash,
+    {
+        let len = keys.len().checked_mul(count);
+        assert_eq!(len, Some(out.len()), "out must hold count hashes per key");
+        if count == 0 {
+            return;
+        }
+        if !self.wide_hash_iter_batch(keys, count, out, Isa::Portable) {
+            self.hash_iter_batch(keys, count, out);
+        }
+    }
+}
+
+/// Same as `wide::advance()`, with all the lanes in a single vector.
+pub(crate) fn advance<T: Number>(
+    x: [u64; LANES],
+    y: [u64; LANES],
+    n: u64,
+    enhanced: bool,
+    out: &mut [T],
+) {
+    let count = out.len() / LANES;
+    let (mut x, mut y) = (u64x4::from_array(x), u64x4::from_array(y));
+    let lanes_n = u64x4::splat(n);
+    let mut rows = rows(out);
+    for i in 0..count {
+        if i > 0 {
+            let step = u64x4::splat(from_u64_mod(i as u64, n));
+            x = add_mod(x, y, lanes_n);
+            if enhanced {
+                y = add_mod(y, step, lanes_n);
+            }
+        }
+        for (row, &h) in rows.iter_mut().zip(x.as_array()) {
+            row[i] = from_lane(h);
+        }
+    }
+}
+
+/// Lane-wise `a + b (mod n)`, for `a, b < n`.
+#[inline]
+fn add_mod(a: u64x4, b: u64x4, n: u64x4) -> u64x4 {
+    let c = n - b;
+    a.simd_ge(c).select(a - c, a + b)
+}
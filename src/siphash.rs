@@ -0,0 +1,169 @@
+//! Keyed SipHash, for keys chosen by an adversary.
+
+use {
+    crate::{sealed, DoubleHashHasher},
+    siphasher::sip::{SipHasher13, SipHasher24},
+    std::{fmt, hash},
+};
+
+/// Number of compression and finalization rounds of SipHash.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SipRounds {
+    /// SipHash-1-3, the variant used by the standard library's hash maps.
+    #[default]
+    Sip13,
+
+    /// SipHash-2-4, the original (more conservative) variant.
+    Sip24,
+}
+
+/// Builder of keyed [`SipHashHasher`]s.
+///
+/// SipHash is a pseudorandom function: as long as its 128-bit key is kept
+/// secret, an adversary controlling the hashed keys cannot predict their hash
+/// values, and thus cannot craft keys colliding on purpose (HashDoS). The key
+/// is not shown by the [`Debug`] implementation.
+///
+/// Available with the `siphash` feature. See
+/// [`DoubleHashHasher::with_siphash`] for a ready-made double hasher.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SipHashBuilder {
+    key: u128,
+    rounds: SipRounds,
+}
+
+impl SipHashBuilder {
+    /// Constructs a new builder, with the given secret key (and SipHash-1-3).
+    pub fn new(key: u128) -> Self {
+        Self {
+            key,
+            rounds: SipRounds::default(),
+        }
+    }
+
+    /// Sets the SipHash variant.
+    pub fn with_rounds(self, rounds: SipRounds) -> Self {
+        Self { rounds, ..self }
+    }
+}
+
+impl fmt::Debug for SipHashBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SipHashBuilder")
+            .field("rounds", &self.rounds)
+            .finish_non_exhaustive()
+    }
+}
+
+impl hash::BuildHasher for SipHashBuilder {
+    type Hasher = SipHashHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        let (k0, k1) = (self.key as u64, (self.key >> 64) as u64);
+        SipHashHasher(match self.rounds {
+            SipRounds::Sip13 => Sip::Sip13(SipHasher13::new_with_keys(k0, k1)),
+            SipRounds::Sip24 => Sip::Sip24(SipHasher24::new_with_keys(k0, k1)),
+        })
+    }
+}
+
+/// Keyed SipHash hasher, see [`SipHashBuilder`].
+#[derive(Debug, Clone)]
+pub struct SipHashHasher(Sip);
+
+#[derive(Debug, Clone)]
+enum Sip {
+    Sip13(SipHasher13),
+    Sip24(SipHasher24),
+}
+
+impl hash::Hasher for SipHashHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        match &mut self.0 {
+            Sip::Sip13(hasher) => hasher.write(bytes),
+            Sip::Sip24(hasher) => hasher.write(bytes),
+        }
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        match &mut self.0 {
+            Sip::Sip13(hasher) => hasher.write_u64(i),
+            Sip::Sip24(hasher) => hasher.write_u64(i),
+        }
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        match &self.0 {
+            Sip::Sip13(hasher) => hasher.finish(),
+            Sip::Sip24(hasher) => hasher.finish(),
+        }
+    }
+}
+
+impl<T> DoubleHashHasher<T, SipHashBuilder> {
+    /// Constructs a double hasher, whose starting hash points are computed
+    /// with keyed SipHash-1-3 (see [`SipHashBuilder`]).
+    ///
+    /// Both keys must be secret, and should be independent (e.g. drawn from a
+    /// cryptographically secure source of randomness). Other SipHash variants
+    /// can be configured with [`with_hash_builders`](Self::with_hash_builders):
+    ///
+    /// ```
+    /// use hash_iter::{DoubleHashHasher, HashIterHasher, SipHashBuilder, SipRounds};
+    ///
+    /// let (key1, key2) = (0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210);
+    /// let hasher = DoubleHashHasher::with_siphash(key1, key2, 1000u64);
+    /// let hashes = hasher.hash_iter(&"hello", 3).collect::<Vec<_>>();
+    /// assert!(hashes.iter().all(|&h| h < 1000));
+    ///
+    /// let hasher = DoubleHashHasher::with_hash_builders(
+    ///     SipHashBuilder::new(key1).with_rounds(SipRounds::Sip24),
+    ///     SipHashBuilder::new(key2).with_rounds(SipRounds::Sip24),
+    ///     1000u64,
+    /// );
+    /// assert_ne!(hasher.hash_iter(&"hello", 3).collect::<Vec<_>>(), hashes);
+    /// ```
+    pub fn with_siphash(key1: u128, key2: u128, n: T) -> Self
+    where
+        T: sealed::Modulus,
+    {
+        Self::with_hash_builders(SipHashBuilder::new(key1), SipHashBuilder::new(key2), n)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<T> DoubleHashHasher<T, SipHashBuilder> {
+    /// Constructs a double hasher for a table of `n` slots, hardened against
+    /// adversarial keys, in a single call.
+    ///
+    /// The starting hash points are computed with SipHash-2-4, keyed with two
+    /// independent random keys, drawn from the operating system once per
+    /// process, and the second one is kept away from degenerate values modulo
+    /// `n` (see [`with_nonzero_h2`](Self::with_nonzero_h2)). Sequences are
+    /// therefore unpredictable, and differ from run to run (so must not be
+    /// persisted).
+    ///
+    /// Available with the `siphash` and `rand` features.
+    ///
+    /// ```
+    /// use hash_iter::{DoubleHashHasher, HashIterHasher};
+    ///
+    /// let hasher = DoubleHashHasher::secure(1024u32);
+    /// assert!(hasher.hash_iter(&"hello", 3).all(|h| h < 1024));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system fails to provide random bytes.
+    pub fn secure(n: T) -> Self
+    where
+        T: sealed::Modulus,
+    {
+        let [key1, key2] = crate::random::process_keys();
+        let builder = |key| SipHashBuilder::new(key).with_rounds(SipRounds::Sip24);
+        Self::with_hash_builders(builder(key1), builder(key2), n).with_nonzero_h2(true)
+    }
+}
@@ -0,0 +1,168 @@
+//! Pluggable formulas for generating hash points.
+
+use {
+    crate::{rem, DoubleHashHasher, Hashes, HashingMode, Number},
+    std::{hash, iter},
+};
+
+/// Formula computing the `i`-th hash point out of the (reduced) start points
+/// of a key.
+///
+/// Implemented by [`HashingMode`] (enhanced and plain double hashing), and by
+/// closures of the form `|h1, h2, i, n| -> T`, so that custom sequences can
+/// reuse the seeding and iteration machinery of this crate.
+pub trait ProbeStrategy<T: Number> {
+    /// Returns the `i`-th hash point, given the start points `h1` and `h2`
+    /// (both in `[0, n)`) and the size of the hash table `n`.
+    ///
+    /// The result is reduced modulo `n`, if needed.
+    fn probe(&self, h1: T, h2: T, i: u64, n: T) -> T;
+}
+
+impl<T: Number> ProbeStrategy<T> for HashingMode {
+    fn probe(&self, h1: T, h2: T, i: u64, n: T) -> T {
+        Hashes::new_u64(h1, h2, n, 0)
+            .with_hashing_mode(*self)
+            .hash_at(i)
+    }
+}
+
+impl<T, F> ProbeStrategy<T> for F
+where
+    T: Number,
+    F: Fn(T, T, u64, T) -> T,
+{
+    fn probe(&self, h1: T, h2: T, i: u64, n: T) -> T {
+        self(h1, h2, i, n)
+    }
+}
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher,
+    H2: hash::BuildHasher,
+{
+    /// Returns an iterator over `count` hash values for the given key,
+    /// computed using the given strategy (instead of the hasher's hashing
+    /// mode).
+    ///
+    /// ```
+    /// use hash_iter::{BuildHashIterHasher, DoubleHashBuilder};
+    ///
+    /// let hasher = DoubleHashBuilder::<u64>::new()
+    ///     .with_n(1000)
+    ///     .build_hash_iter_hasher();
+    /// // Double hashing with an odd step.
+    /// let hashes = hasher.probes(&"hello", 10, |h1, h2, i, n| (h1 + i * (h2 | 1)) % n);
+    /// assert_eq!(hashes.count(), 10);
+    /// ```
+    pub fn probes<K, S>(&self, key: &K, count: u64, strategy: S) -> Probes<T, S>
+    where
+        K: hash::Hash + ?Sized,
+        S: ProbeStrategy<T>,
+    {
+        let hashes = self.hashes(key, count);
+        Probes {
+            h1: hashes.hash1(),
+            h2: hashes.hash2(),
+            n: hashes.n(),
+            k: count,
+            cnt: 0,
+            strategy,
+        }
+    }
+}
+
+/// Iterator over hash points generated by a [`ProbeStrategy`].
+///
+/// Created by [`DoubleHashHasher::probes`].
+#[derive(Debug, Clone)]
+pub struct Probes<T, S> {
+    /// The first start point, reduced modulo `n`.
+    h1: T,
+
+    /// The second start point, reduced modulo `n`.
+    h2: T,
+
+    /// The size of the hash table.
+    n: T,
+
+    /// The number of hash points to generate (decremented when iterating from
+    /// the back).
+    k: u64,
+
+    /// The current number of hash points generated (from the front).
+    cnt: u64,
+
+    /// The formula computing hash points.
+    strategy: S,
+}
+
+impl<T, S> Probes<T, S>
+where
+    T: Number,
+    S: ProbeStrategy<T>,
+{
+    /// Computes the `i`-th hash point, reduced modulo `n` (unless `n = 0`,
+    /// i.e. the hash points cover the whole range of `T`).
+    fn point_at(&self, i: u64) -> T {
+        let point = self.strategy.probe(self.h1, self.h2, i, self.n);
+        T::from_repr(rem(point.to_repr(), self.n.to_repr()))
+    }
+}
+
+impl<T, S> Iterator for Probes<T, S>
+where
+    T: Number,
+    S: ProbeStrategy<T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cnt == self.k {
+            return None;
+        }
+
+        self.cnt += 1;
+        Some(self.point_at(self.cnt - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.k - self.cnt) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl<T, S> DoubleEndedIterator for Probes<T, S>
+where
+    T: Number,
+    S: ProbeStrategy<T>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.cnt == self.k {
+            return None;
+        }
+
+        self.k -= 1;
+        Some(self.point_at(self.k))
+    }
+}
+
+impl<T, S> iter::FusedIterator for Probes<T, S>
+where
+    T: Number,
+    S: ProbeStrategy<T>,
+{
+}
+
+/// See the note on `ExactSizeIterator` for [`Hashes`].
+#[cfg(not(any(target_pointer_width = "16", target_pointer_width = "32")))]
+impl<T, S> ExactSizeIterator for Probes<T, S>
+where
+    T: Number,
+    S: ProbeStrategy<T>,
+{
+}
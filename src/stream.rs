@@ -0,0 +1,32 @@
+//! Adapter hashing the keys of an asynchronous stream, available with the
+//! `futures` feature.
+
+use {
+    crate::{DoubleHashHasher, HashIterHasher, Number},
+    futures_util::{Stream, StreamExt},
+    std::hash,
+};
+
+impl<T, H1, H2> DoubleHashHasher<T, H1, H2>
+where
+    T: Number,
+    H1: hash::BuildHasher + Clone,
+    H2: hash::BuildHasher + Clone,
+{
+    /// Adapts a stream of keys into a stream of `(key, hashes)` pairs, where
+    /// `hashes` holds `count` hash values of the key.
+    ///
+    /// Keys are hashed one at a time, as they arrive, so the executor is never
+    /// blocked on a large batch.
+    pub fn hash_stream<S>(&self, keys: S, count: usize) -> impl Stream<Item = (S::Item, Vec<T>)>
+    where
+        S: Stream,
+        S::Item: hash::Hash,
+    {
+        let hasher = self.clone();
+        keys.map(move |key| {
+            let hashes = hasher.hash_iter(&key, count).collect();
+            (key, hashes)
+        })
+    }
+}
@@ -0,0 +1,177 @@
+//! Simple tabulation hashing, a 3-independent alternative to XXH3.
+
+use {
+    crate::{sealed, splitmix64, DoubleHashHasher, SeedableBuildHasher},
+    std::{array, fmt, hash, sync::Arc},
+};
+
+/// One table of random words per byte of a 64-bit word.
+type Tables = [[u64; 256]; 8];
+
+/// Builder of [`TabulationHasher`]s.
+///
+/// Holds the lookup tables (16 KiB, filled out of the seed), which are shared
+/// by the hashers it builds. Can be used in place of the default XXH3 hash
+/// builders, see [`DoubleHashHasher::with_tabulation`].
+#[derive(Clone)]
+pub struct TabulationBuilder {
+    seed: u64,
+    tables: Arc<Tables>,
+}
+
+impl TabulationBuilder {
+    /// Constructs a new builder, with the default seed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the seed, out of which the lookup tables are filled.
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self {
+            seed,
+            tables: tables(seed),
+        }
+    }
+}
+
+impl Default for TabulationBuilder {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            tables: tables(0),
+        }
+    }
+}
+
+/// Fills the lookup tables out of the seed.
+fn tables(seed: u64) -> Arc<Tables> {
+    let word = |j: usize, b: usize| splitmix64(seed, (256 * j + b) as u64);
+    Arc::new(array::from_fn(|j| array::from_fn(|b| word(j, b))))
+}
+
+impl PartialEq for TabulationBuilder {
+    fn eq(&self, other: &Self) -> bool {
+        self.seed == other.seed
+    }
+}
+
+impl Eq for TabulationBuilder {}
+
+impl fmt::Debug for TabulationBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TabulationBuilder")
+            .field("seed", &self.seed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl hash::BuildHasher for TabulationBuilder {
+    type Hasher = TabulationHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        TabulationHasher {
+            tables: Arc::clone(&self.tables),
+            acc: 0,
+        }
+    }
+}
+
+impl SeedableBuildHasher for TabulationBuilder {
+    fn with_seed(self, seed: u64) -> Self {
+        TabulationBuilder::with_seed(self, seed)
+    }
+}
+
+/// Simple tabulation hasher (Zobrist, Carter and Wegman; analyzed by Pǎtraşcu
+/// and Thorup).
+///
+/// A 64-bit word `x = x_0 x_1 ... x_7` (split into bytes) is hashed as
+/// `T_0[x_0] ^ T_1[x_1] ^ ... ^ T_7[x_7]`, where `T_j` are tables of random
+/// words. This family is 3-independent, and is known to behave like truly
+/// random hashing in linear probing, cuckoo hashing and Bloom filters.
+///
+/// The key is consumed as a sequence of 64-bit words (see
+/// [`MultiplyShiftHasher`](crate::MultiplyShiftHasher) for how bytes are
+/// split into words), which are chained: each word is XORed with the hash of
+/// the preceding ones before being hashed. The guarantees above are only
+/// proven for keys hashed as a single word (integers up to 64 bits).
+#[derive(Clone)]
+pub struct TabulationHasher {
+    tables: Arc<Tables>,
+    acc: u64,
+}
+
+impl TabulationHasher {
+    /// Adds the next 64-bit word of the key.
+    #[inline]
+    fn write_word(&mut self, word: u64) {
+        let x = self.acc ^ word;
+        self.acc = self
+            .tables
+            .iter()
+            .enumerate()
+            .fold(0, |h, (j, table)| h ^ table[(x >> (8 * j)) as u8 as usize]);
+    }
+}
+
+impl fmt::Debug for TabulationHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TabulationHasher")
+            .field("acc", &self.acc)
+            .finish_non_exhaustive()
+    }
+}
+
+impl hash::Hasher for TabulationHasher {
+    fn finish(&self) -> u64 {
+        self.acc
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.write_word(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.write_word(i.into());
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write_word(i.into());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write_word(i.into());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write_word(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_word(i as u64);
+    }
+}
+
+impl<T> DoubleHashHasher<T, TabulationBuilder> {
+    /// Constructs a double hasher, whose starting hash points are computed
+    /// with simple tabulation hashing, with tables filled out of two
+    /// independent seeds derived from `seed`.
+    ///
+    /// ```
+    /// use hash_iter::{DoubleHashHasher, HashIterHasher};
+    ///
+    /// let hasher = DoubleHashHasher::with_tabulation(42, 1000u64);
+    /// assert!(hasher.hash_iter(&12345u64, 3).all(|h| h < 1000));
+    /// ```
+    pub fn with_tabulation(seed: u64, n: T) -> Self
+    where
+        T: sealed::Modulus,
+    {
+        let builder = |i| TabulationBuilder::new().with_seed(splitmix64(seed, i));
+        Self::with_hash_builders(builder(0), builder(1), n)
+    }
+}
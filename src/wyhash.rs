@@ -0,0 +1,61 @@
+//! Wyhash hash builders.
+
+use {
+    crate::{sealed, splitmix64, DoubleHashHasher, SeedableBuildHasher},
+    ::wyhash::WyHash,
+    std::hash,
+};
+
+/// Builder of seeded [wyhash](https://crates.io/crates/wyhash) hashers.
+///
+/// Available with the `wyhash` feature. See
+/// [`DoubleHashHasher::with_wyhash`] for a ready-made double hasher.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WyHashBuilder {
+    seed: u64,
+}
+
+impl WyHashBuilder {
+    /// Constructs a new builder, with the default seed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the seed.
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl hash::BuildHasher for WyHashBuilder {
+    type Hasher = WyHash;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        WyHash::with_seed(self.seed)
+    }
+}
+
+impl SeedableBuildHasher for WyHashBuilder {
+    fn with_seed(self, seed: u64) -> Self {
+        WyHashBuilder::with_seed(self, seed)
+    }
+}
+
+impl<T> DoubleHashHasher<T, WyHashBuilder> {
+    /// Constructs a double hasher, whose starting hash points are computed
+    /// with wyhash, seeded by two independent seeds derived from `seed`.
+    ///
+    /// ```
+    /// use hash_iter::{DoubleHashHasher, HashIterHasher};
+    ///
+    /// let hasher = DoubleHashHasher::with_wyhash(42, 1000u64);
+    /// assert!(hasher.hash_iter(&"hello", 3).all(|h| h < 1000));
+    /// ```
+    pub fn with_wyhash(seed: u64, n: T) -> Self
+    where
+        T: sealed::Modulus,
+    {
+        let builder = |i| WyHashBuilder::new().with_seed(splitmix64(seed, i));
+        Self::with_hash_builders(builder(0), builder(1), n)
+    }
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "ahash")]
+
+use {
+    ahash::RandomState,
+    hash_iter::{DoubleHashHasher, HashIterHasher, Hashes},
+};
+
+#[test]
+fn with_ahash() {
+    let hasher = DoubleHashHasher::with_ahash(42, 1000u64);
+    let again = DoubleHashHasher::with_ahash(42, 1000u64);
+    assert!(hasher
+        .hash_iter(&"hello", 10)
+        .eq(again.hash_iter(&"hello", 10)));
+
+    let other = DoubleHashHasher::with_ahash(43, 1000u64);
+    assert!(hasher
+        .hash_iter(&"hello", 10)
+        .ne(other.hash_iter(&"hello", 10)));
+}
+
+#[test]
+fn shared_random_state() {
+    let (state1, state2) = (
+        RandomState::with_seeds(1, 2, 3, 4),
+        RandomState::with_seeds(5, 6, 7, 8),
+    );
+    let (h1, h2) = (state1.hash_one(42u64), state2.hash_one(42u64));
+    let hasher = DoubleHashHasher::with_hash_builders(state1, state2, 1000u64);
+    assert!(hasher
+        .hash_iter(&42u64, 10)
+        .eq(Hashes::new(h1, h2, 1000, 10)));
+}
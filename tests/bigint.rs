@@ -0,0 +1,57 @@
+#![cfg(feature = "num-bigint")]
+
+use {
+    hash_iter::{BigHashes, DoubleHashHasher, HashIterHasher, HashingMode},
+    num_bigint::BigUint,
+    xxhash_rust::xxh3::Xxh3Builder,
+};
+
+#[test]
+fn big_hashes_closed_form() {
+    let n: BigUint = BigUint::from(1u8) << 300;
+    let x: BigUint = (BigUint::from(1u8) << 299) + 12345u32;
+    let y: BigUint = (BigUint::from(1u8) << 298) + 67890u32;
+
+    let hashes = BigHashes::new(x.clone(), y.clone(), n.clone(), 100);
+    for (i, hash) in hashes.enumerate() {
+        let i = BigUint::from(i);
+        let expected = (&x + &i * &y + (i.pow(3) - &i) / 6u8) % &n;
+        assert_eq!(hash, expected);
+    }
+}
+
+#[test]
+fn big_hash_iter() {
+    let n: BigUint = (BigUint::from(1u8) << 1000) - 1u8;
+    let hasher = DoubleHashHasher::with_hash_builders(
+        Xxh3Builder::new().with_seed(12345),
+        Xxh3Builder::new().with_seed(67890),
+        n.clone(),
+    );
+
+    let hashes = hasher.hash_iter(&"hello", 10).collect::<Vec<_>>();
+    assert_eq!(hashes.len(), 10);
+    assert!(hashes.iter().all(|h| h < &n));
+
+    // Starting points cover the whole range, not just the low 64 bits.
+    assert!(hashes.iter().any(|h| h.bits() > 900));
+}
+
+#[test]
+fn big_hash_iter_plain() {
+    let n: BigUint = (BigUint::from(1u8) << 1000) - 1u8;
+    let hasher = DoubleHashHasher::with_hash_builders(
+        Xxh3Builder::new().with_seed(12345),
+        Xxh3Builder::new().with_seed(67890),
+        n.clone(),
+    );
+    let plain = hasher.clone().with_hashing_mode(HashingMode::Plain);
+
+    // Same start points, but the difference between hash points is constant.
+    let hashes = plain.hash_iter(&"hello", 10).collect::<Vec<_>>();
+    let mut enhanced = hasher.hash_iter(&"hello", 2);
+    let (x, y) = (enhanced.next().unwrap(), enhanced.next().unwrap());
+    for (i, hash) in hashes.into_iter().enumerate() {
+        assert_eq!(hash, (&x + (&y + &n - &x) * BigUint::from(i)) % &n);
+    }
+}
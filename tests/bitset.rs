@@ -0,0 +1,54 @@
+#![cfg(feature = "fixed-bitset")]
+
+use {
+    fixedbitset::FixedBitSet,
+    hash_iter::{BuildHashIterHasher, DoubleHashBuilder, HashIterHasher},
+};
+
+#[test]
+fn bitset_insert_and_contains() {
+    let mut bitset = FixedBitSet::with_capacity(1024);
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(1024)
+        .build_hash_iter_hasher();
+
+    assert!(!hasher.bitset_contains(&"foo", 7, &bitset));
+    assert!(!hasher.bitset_insert(&"foo", 7, &mut bitset));
+    assert!(hasher.bitset_contains(&"foo", 7, &bitset));
+    assert!(hasher.bitset_insert(&"foo", 7, &mut bitset));
+
+    assert!(hasher
+        .hash_iter(&"foo", 7)
+        .all(|idx| bitset.contains(idx as usize)));
+    assert!(bitset.count_ones(..) <= 7);
+}
+
+#[test]
+#[should_panic(expected = "n must not exceed the length of the bitset")]
+fn bitset_too_small() {
+    let mut bitset = FixedBitSet::with_capacity(100);
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(1024)
+        .build_hash_iter_hasher();
+    hasher.bitset_insert(&"foo", 7, &mut bitset);
+}
+
+#[test]
+#[should_panic(expected = "n must not be the full range")]
+fn bitset_full_range() {
+    let mut bitset = FixedBitSet::with_capacity(64);
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_full_range()
+        .build_hash_iter_hasher();
+    hasher.bitset_insert(&"hello", 3, &mut bitset);
+}
+
+#[test]
+#[should_panic(expected = "n must not be the full range")]
+fn bitset_contains_full_range() {
+    let bitset = FixedBitSet::with_capacity(64);
+    let hasher = DoubleHashBuilder::<u8>::new()
+        .with_full_range()
+        .build_hash_iter_hasher();
+    hasher.bitset_contains(&"hello", 3, &bitset);
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "blake3")]
+
+use {
+    hash_iter::{Blake3Builder, DoubleHashHasher, HashIterHasher, Hashes},
+    std::hash::BuildHasher,
+};
+
+#[test]
+fn blake3_builder() {
+    let key = [7; 32];
+    let builder = Blake3Builder::new(key);
+    let expected = blake3::keyed_hash(&key, &42u64.to_le_bytes());
+    assert_eq!(
+        builder.hash_one(42u64).to_le_bytes(),
+        expected.as_bytes()[..8]
+    );
+
+    // The key is not leaked.
+    assert_eq!(format!("{builder:?}"), "Blake3Builder { .. }");
+}
+
+#[test]
+fn with_blake3() {
+    let key = [7; 32];
+    let hasher = DoubleHashHasher::with_blake3(key, 1000u64);
+    let (key1, key2) = (
+        blake3::derive_key("hash-iter 2026-10-16 first start point", &key),
+        blake3::derive_key("hash-iter 2026-10-16 second start point", &key),
+    );
+    let (h1, h2) = (
+        Blake3Builder::new(key1).hash_one(42u64),
+        Blake3Builder::new(key2).hash_one(42u64),
+    );
+    assert!(hasher
+        .hash_iter(&42u64, 10)
+        .eq(Hashes::new(h1, h2, 1000, 10)));
+
+    let other = DoubleHashHasher::with_blake3([8; 32], 1000u64);
+    assert!(other.hash_iter(&42u64, 10).ne(hasher.hash_iter(&42u64, 10)));
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "derive")]
+
+use hash_iter::{DoubleHashHasher, PortableKey};
+
+fn encoding<K: PortableKey + ?Sized>(key: &K) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    key.encode(&mut bytes);
+    bytes
+}
+
+#[derive(PortableKey)]
+struct User {
+    id: u64,
+    name: String,
+}
+
+#[derive(PortableKey)]
+struct Pair<A, B>(A, B);
+
+#[derive(PortableKey)]
+struct Unit;
+
+#[derive(PortableKey)]
+enum Event {
+    Login { user: u64 },
+    Message(u64, String),
+    Logout,
+}
+
+#[test]
+fn derive_struct() {
+    let user = User {
+        id: 42,
+        name: "alice".to_string(),
+    };
+    assert_eq!(encoding(&user), encoding(&(42u64, "alice")));
+    assert_eq!(
+        encoding(&Pair(1u8, Some(2u16))),
+        encoding(&(1u8, Some(2u16)))
+    );
+    assert!(encoding(&Unit).is_empty());
+
+    let hasher = DoubleHashHasher::<u64>::new();
+    assert!(hasher
+        .hash_iter_portable(&user, 10)
+        .eq(hasher.hash_iter_portable(&(42u64, "alice"), 10)));
+}
+
+#[test]
+fn derive_enum() {
+    assert_eq!(encoding(&Event::Login { user: 7 }), encoding(&(0u32, 7u64)));
+    assert_eq!(
+        encoding(&Event::Message(7, "hi".to_string())),
+        encoding(&(1u32, 7u64, "hi"))
+    );
+    assert_eq!(encoding(&Event::Logout), encoding(&2u32));
+}
@@ -1,4 +1,21 @@
-use hash_iter::{BuildHashIterHasher, DoubleHashBuilder, DoubleHashHasher, HashIterHasher};
+use hash_iter::{
+    BuildError,
+    BuildHashIterHasher,
+    CuckooHash,
+    DoubleHashBuilder,
+    DoubleHashHasher,
+    FeistelPermutation,
+    HashFamily,
+    HashIterHasher,
+    Hashes,
+    HashingMode,
+    KeyHashing,
+    MultiplyShiftBuilder,
+    PortableKey,
+    RawBytes,
+    Reduction,
+    TabulationBuilder,
+};
 
 #[test]
 fn default_config() {
@@ -7,10 +24,12 @@ fn default_config() {
         let hasher = DoubleHashHasher::new();
         let hashes = hasher.hash_iter(&"hello", 3).collect::<Vec<_>>();
 
+        // The third value was `5246933596417309480` in 1.x, whose sums could
+        // overflow (see `sequence_compat`).
         assert_eq!(hashes, vec![
             10179864958193109059,
             16936771314159985077,
-            5246933596417309480
+            5246933596417309481
         ]);
     }
 
@@ -23,7 +42,7 @@ fn default_config() {
         assert_eq!(hashes, vec![
             10179864958193109059,
             16936771314159985077,
-            5246933596417309480
+            5246933596417309481
         ]);
     }
 }
@@ -40,10 +59,148 @@ fn custom_config() {
     assert_eq!(hashes, vec![
         10179864958193109059,
         16936771314159985077,
-        5246933596417309480
+        5246933596417309481
     ]);
 }
 
+#[test]
+fn with_seeds() {
+    let hasher = DoubleHashHasher::with_seeds(12345, 67890);
+    assert!(hasher
+        .hash_iter(&"hello", 10)
+        .eq(DoubleHashHasher::new().hash_iter(&"hello", 10)));
+
+    let hasher = DoubleHashHasher::with_seeds_and_n(1, 2, 1000u32);
+    let expected = DoubleHashBuilder::<u32>::new()
+        .with_seed1(1)
+        .with_seed2(2)
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    assert!(hasher
+        .hash_iter(&"hello", 10)
+        .eq(expected.hash_iter(&"hello", 10)));
+    assert!(!hasher
+        .hash_iter(&"hello", 10)
+        .eq(DoubleHashHasher::with_seeds_and_n(2, 1, 1000u32).hash_iter(&"hello", 10)));
+}
+
+#[test]
+fn master_seed() {
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_seed(42)
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    let other = DoubleHashBuilder::<u64>::new()
+        .with_seed(43)
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    assert!(!hasher
+        .hash_iter(&"hello", 10)
+        .eq(other.hash_iter(&"hello", 10)));
+
+    // The derived seeds differ, and so do the start points.
+    let hashes = hasher.hashes(&"hello", 10);
+    assert_ne!(hashes.hash1(), hashes.hash2());
+
+    // Seeds derived from strings.
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_seed_str("prod-cluster-eu-1")
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    let other = DoubleHashBuilder::<u64>::new()
+        .with_seed_bytes(b"prod-cluster-eu-1")
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    assert!(hasher
+        .hash_iter(&"hello", 10)
+        .eq(other.hash_iter(&"hello", 10)));
+    let other = DoubleHashBuilder::<u64>::new()
+        .with_seed_str("prod-cluster-eu-2")
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    assert!(!hasher
+        .hash_iter(&"hello", 10)
+        .eq(other.hash_iter(&"hello", 10)));
+
+    // Same derivation for other hash builders.
+    let hasher = DoubleHashBuilder::<u64>::new_with::<MultiplyShiftBuilder>()
+        .with_seed(42)
+        .build_hash_iter_hasher();
+    let other = DoubleHashBuilder::<u64>::new_with::<MultiplyShiftBuilder>()
+        .with_seed(42)
+        .build_hash_iter_hasher();
+    assert!(hasher
+        .hash_iter(&"hello", 10)
+        .eq(other.hash_iter(&"hello", 10)));
+}
+
+#[test]
+fn identical_seeds() {
+    let builder = DoubleHashBuilder::<u64>::new().with_seed1(7).with_seed2(7);
+    assert_eq!(
+        builder.try_build_hash_iter_hasher().err(),
+        Some(BuildError::IdenticalHashBuilders)
+    );
+    assert_eq!(
+        BuildError::IdenticalHashBuilders.to_string(),
+        "hash builders must differ (e.g. seed1 != seed2), or h1 always equals h2"
+    );
+
+    // Still accepted by the infallible build.
+    let hashes = builder.build_hash_iter_hasher().hashes(&"hello", 10);
+    assert_eq!(hashes.hash1(), hashes.hash2());
+
+    // Distinct seeds are fine.
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .try_build_hash_iter_hasher()
+        .unwrap();
+    assert!(hasher
+        .hash_iter(&"hello", 10)
+        .eq(DoubleHashHasher::new().hash_iter(&"hello", 10)));
+}
+
+#[test]
+fn typed_seeds() {
+    // Seeds are of the output type, and widened into 64-bit ones.
+    let seed1: u32 = 1;
+    let seed2: u32 = 2;
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_seed1(seed1)
+        .with_seed2(seed2)
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    assert!(hasher
+        .hash_iter(&"hello", 10)
+        .eq(DoubleHashHasher::with_seeds_and_n(1, 2, 1000u32).hash_iter(&"hello", 10)));
+
+    // Negative seeds are taken as their two's complement.
+    let hasher = DoubleHashBuilder::<i64>::new()
+        .with_seed1(-1)
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    assert!(hasher
+        .hash_iter(&"hello", 10)
+        .eq(DoubleHashHasher::with_seeds_and_n(u64::MAX, 67890, 1000i64).hash_iter(&"hello", 10)));
+}
+
+#[test]
+#[should_panic(expected = "seed must fit into 64 bits")]
+fn typed_seeds_too_wide() {
+    let _ = DoubleHashBuilder::<u128>::new().with_seed1(1 << 64);
+}
+
+#[test]
+fn identical_seeds_single_hash() {
+    // Only the first hash builder is used.
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_seed1(7)
+        .with_seed2(7)
+        .with_key_hashing(KeyHashing::Mixed)
+        .build_hash_iter_hasher();
+    let hashes = hasher.hashes(&"hello", 10);
+    assert_ne!(hashes.hash1(), hashes.hash2());
+}
+
 #[test]
 fn custom_hash_builders() {
     use xxhash_rust::xxh3::Xxh3Builder;
@@ -58,10 +215,142 @@ fn custom_hash_builders() {
     assert_eq!(hashes, vec![
         10179864958193109059,
         16936771314159985077,
-        5246933596417309480
+        5246933596417309481
     ]);
 }
 
+#[test]
+fn custom_secret() {
+    let mut secret = [0u8; 192];
+    for (i, byte) in secret.iter_mut().enumerate() {
+        *byte = (i as u8).wrapping_mul(167).wrapping_add(13);
+    }
+    let builder = DoubleHashBuilder::<u64>::new().with_n(1000);
+    let hasher = builder
+        .with_seed1(1)
+        .with_secret(&secret)
+        .with_seed2(2)
+        .build_hash_iter_hasher();
+
+    // The secret can be set before or after the seeds.
+    let reordered = builder
+        .with_secret(&secret)
+        .with_seed1(1)
+        .with_seed2(2)
+        .build_hash_iter_hasher();
+    let seeded = builder
+        .with_seed1(1)
+        .with_seed2(2)
+        .with_secret(&secret)
+        .build_hash_iter_hasher();
+    for other in [reordered, seeded] {
+        assert!(hasher
+            .hash_iter(&"hello", 10)
+            .eq(other.hash_iter(&"hello", 10)));
+    }
+
+    // The secret changes the sequences of short keys, as well as long ones.
+    let key = "a key long enough to go past the short input path of XXH3, which is 240 bytes long \
+               so this string needs some padding, padding, padding, padding, padding, padding, \
+               padding, padding, padding, padding and then some more padding, padding, padding.";
+    let default = builder.with_seed1(1).with_seed2(2).build_hash_iter_hasher();
+    let mut other_secret = secret;
+    other_secret[0] ^= 1;
+    let other = builder
+        .with_seed1(1)
+        .with_seed2(2)
+        .with_secret(&other_secret)
+        .build_hash_iter_hasher();
+    for key in ["hello", key] {
+        assert!(!hasher.hash_iter(&key, 10).eq(default.hash_iter(&key, 10)));
+        assert!(!hasher.hash_iter(&key, 10).eq(other.hash_iter(&key, 10)));
+    }
+
+    // The seeds still matter, and tell the start points apart.
+    let reseeded = builder
+        .with_secret(&secret)
+        .with_seed1(1)
+        .with_seed2(3)
+        .build_hash_iter_hasher();
+    assert!(!hasher
+        .hash_iter(&"hello", 10)
+        .eq(reseeded.hash_iter(&"hello", 10)));
+    let same_seeds = builder.with_secret(&secret).with_seed1(1).with_seed2(1);
+    assert!(same_seeds.try_build_hash_iter_hasher().is_err());
+}
+
+#[test]
+fn new_with() {
+    // Default seeds.
+    let hasher = DoubleHashBuilder::<u64>::new_with::<MultiplyShiftBuilder>()
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    let expected = DoubleHashHasher::with_hash_builders(
+        MultiplyShiftBuilder::new().with_seed(12345),
+        MultiplyShiftBuilder::new().with_seed(67890),
+        1000,
+    );
+    assert!(hasher
+        .hash_iter(&"hello", 10)
+        .eq(expected.hash_iter(&"hello", 10)));
+
+    // Custom seeds.
+    let hasher = DoubleHashBuilder::<u64>::new_with::<TabulationBuilder>()
+        .with_seed1(1)
+        .with_seed2(2)
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    let expected = DoubleHashHasher::with_hash_builders(
+        TabulationBuilder::new().with_seed(1),
+        TabulationBuilder::new().with_seed(2),
+        1000,
+    );
+    assert!(hasher
+        .hash_iter(&"hello", 10)
+        .eq(expected.hash_iter(&"hello", 10)));
+
+    // XXH3 is the default.
+    assert!(
+        DoubleHashBuilder::<u64>::new_with::<xxhash_rust::xxh3::Xxh3Builder>()
+            .build_hash_iter_hasher()
+            .hash_iter(&"hello", 10)
+            .eq(DoubleHashHasher::new().hash_iter(&"hello", 10))
+    );
+}
+
+#[test]
+fn mixed_hash_builders() {
+    use {std::hash::BuildHasher, xxhash_rust::xxh3::Xxh3Builder};
+
+    // XXH3 for the first start point, multiply-shift for the second one.
+    let builder2 = MultiplyShiftBuilder::new().with_seed(7);
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_seed1(1)
+        .with_hash_builder2(builder2)
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    let h1 = Xxh3Builder::new().with_seed(1).hash_one("hello");
+    let h2 = builder2.hash_one("hello");
+    assert!(hasher
+        .hash_iter(&"hello", 10)
+        .eq(Hashes::new(h1, h2, 1000, 10)));
+
+    // The configuration is kept when replacing hash builders.
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_n(1000)
+        .with_hashing_mode(HashingMode::Plain)
+        .with_hash_builder1(builder2)
+        .build_hash_iter_hasher();
+    assert_eq!(
+        hasher.hashes(&"hello", 10).hashing_mode(),
+        HashingMode::Plain
+    );
+    assert_eq!(
+        hasher.hashes(&"hello", 10).hash1(),
+        builder2.hash_one("hello") % 1000
+    );
+}
+
 #[test]
 fn use_as_struct_field() {
     {
@@ -87,7 +376,7 @@ fn use_as_struct_field() {
         assert_eq!(hashes, vec![
             2604207548944960858,
             14475308512507584086,
-            7899665402360655699
+            7899665402360655700
         ]);
     }
     {
@@ -114,7 +403,2093 @@ fn use_as_struct_field() {
         assert_eq!(hashes, vec![
             2604207548944960858,
             14475308512507584086,
-            7899665402360655699
+            7899665402360655700
         ]);
     }
+    {
+        // The second builder type defaults to the first one.
+        struct Foo {
+            hasher: DoubleHashHasher<u32, MultiplyShiftBuilder>,
+        }
+
+        let foo = Foo {
+            hasher: DoubleHashHasher::with_hash_builders(
+                MultiplyShiftBuilder::new().with_seed(1),
+                MultiplyShiftBuilder::new().with_seed(2),
+                1000,
+            ),
+        };
+        assert!(foo.hasher.hash_iter(&42u64, 3).all(|h| h < 1000));
+    }
+}
+
+#[test]
+fn u16_hashes() {
+    let hasher = DoubleHashBuilder::<u16>::new()
+        .with_n(1000)
+        .build_hash_iter_hasher();
+
+    let hashes = hasher.hash_iter(&"hello", 3).collect::<Vec<_>>();
+    assert_eq!(hashes, vec![115, 629, 144]);
+    assert!(hashes.iter().all(|&h| h < 1000));
+
+    // Default `n` is capped at `u16::MAX`.
+    let hasher = DoubleHashBuilder::<u16>::new().build_hash_iter_hasher();
+    let hashes = hasher.hash_iter(&"hello", 3).collect::<Vec<_>>();
+    assert_eq!(hashes, vec![2115, 8629, 15144]);
+}
+
+#[test]
+fn u8_hashes() {
+    // Micro Bloom filter: 255 bits, 7 probes per key.
+    let hasher = DoubleHashBuilder::<u8>::new().build_hash_iter_hasher();
+
+    let mut filter = [false; u8::MAX as usize];
+    for key in ["foo", "bar", "baz"] {
+        for idx in hasher.hash_iter(&key, 7) {
+            filter[idx as usize] = true;
+        }
+    }
+    for key in ["foo", "bar", "baz"] {
+        assert!(hasher.hash_iter(&key, 7).all(|idx| filter[idx as usize]));
+    }
+
+    // `[67, 181, 40]` in 1.x, whose sums could overflow (see `sequence_compat`).
+    let hashes = hasher.hash_iter(&"hello", 3).collect::<Vec<_>>();
+    assert_eq!(hashes, vec![67, 181, 41]);
+}
+
+#[test]
+fn sequence_compat() {
+    use {
+        std::hash::BuildHasher,
+        xxhash_rust::xxh3::{xxh3_64_with_seed, Xxh3Builder},
+    };
+
+    /// The 1.x recurrence, which wrapped sums before reducing them.
+    fn legacy(mut x: u64, mut y: u64, n: u64, k: u64) -> Vec<u64> {
+        let mut hashes = vec![x % n];
+        for cnt in 1..k {
+            x = x.wrapping_add(y) % n;
+            y = y.wrapping_add(cnt) % n;
+            hashes.push(x);
+        }
+        hashes
+    }
+
+    // Sums never overflow for power-of-two `n` up to `2^63`: sequences are
+    // the same as in 1.x.
+    for n in [1 << 10, 1 << 40, 1 << 63] {
+        let hasher = DoubleHashBuilder::<u64>::new()
+            .with_n(n)
+            .build_hash_iter_hasher();
+        for key in 0..1000u64 {
+            let bytes = key.to_le_bytes();
+            let (h1, h2) = (
+                xxh3_64_with_seed(&bytes, 12345),
+                xxh3_64_with_seed(&bytes, 67890),
+            );
+            let hashes = hasher.hash_iter(&RawBytes(bytes), 20).collect::<Vec<_>>();
+            assert_eq!(hashes, legacy(h1, h2, n, 20));
+        }
+    }
+
+    // Otherwise, sequences where sums overflowed differ.
+    let (h1, h2) = (
+        Xxh3Builder::new().with_seed(12345).hash_one("hello"),
+        Xxh3Builder::new().with_seed(67890).hash_one("hello"),
+    );
+    assert_eq!(legacy(h1, h2, u64::MAX, 3)[2], 5246933596417309480);
+}
+
+#[test]
+fn usize_hashes() {
+    // The `usize` sequence is computed in `u64`, so it matches the `u64` one
+    // for the same `n`, regardless of the target's pointer width.
+    let n = 1_000_003;
+    let hasher = DoubleHashBuilder::<usize>::new()
+        .with_n(n)
+        .build_hash_iter_hasher();
+    let hasher64 = DoubleHashBuilder::<u64>::new()
+        .with_n(n as u64)
+        .build_hash_iter_hasher();
+
+    let data = vec![0u8; n];
+    for key in ["foo", "bar", "baz"] {
+        let hashes = hasher.hash_iter(&key, 10).collect::<Vec<_>>();
+        let hashes64 = hasher64.hash_iter(&key, 10).collect::<Vec<_>>();
+        assert!(hashes.iter().zip(&hashes64).all(|(&a, &b)| a as u64 == b));
+
+        // Indices can be used directly, without casting.
+        assert!(hashes.iter().all(|&idx| data[idx] == 0));
+    }
+}
+
+#[test]
+fn generic_over_output_type() {
+    use hash_iter::Number;
+
+    fn bloom_positions<T: Number>(hasher: &impl HashIterHasher<T>, key: &str) -> Vec<T> {
+        hasher.hash_iter(&key, 3).collect()
+    }
+
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    let positions: Vec<u32> = bloom_positions(&hasher, "hello");
+    assert!(positions.iter().all(|&p| p < 1000));
+
+    let hasher = DoubleHashHasher::new();
+    let positions: Vec<u64> = bloom_positions(&hasher, "hello");
+    assert_eq!(positions, vec![
+        10179864958193109059,
+        16936771314159985077,
+        5246933596417309481
+    ]);
+}
+
+#[test]
+fn i64_hashes() {
+    // Default `n` is `i64::MAX`, so all values are non-negative.
+    let hasher = DoubleHashBuilder::<i64>::new().build_hash_iter_hasher();
+    let hasher64 = DoubleHashBuilder::<u64>::new()
+        .with_n(i64::MAX as u64)
+        .build_hash_iter_hasher();
+
+    for key in ["foo", "bar", "baz"] {
+        let hashes = hasher.hash_iter(&key, 100).collect::<Vec<_>>();
+        let hashes64 = hasher64.hash_iter(&key, 100).collect::<Vec<_>>();
+        assert!(hashes.iter().all(|&h| h >= 0));
+        assert!(hashes.iter().zip(&hashes64).all(|(&a, &b)| a as u64 == b));
+    }
+
+    // Custom `n`.
+    let hasher = DoubleHashBuilder::<i64>::new()
+        .with_n(1 << 20)
+        .build_hash_iter_hasher();
+    assert!(hasher
+        .hash_iter(&"hello", 100)
+        .all(|h| (0..1 << 20).contains(&h)));
+}
+
+#[test]
+fn u128_hashes() {
+    let hasher = DoubleHashBuilder::<u128>::new()
+        .with_n(u128::MAX)
+        .build_hash_iter_hasher();
+
+    // Upper 64 bits of the hash points are populated as well.
+    let hashes = hasher.hash_iter(&"hello", 10).collect::<Vec<_>>();
+    assert!(hashes.iter().all(|&h| h > u64::MAX as u128));
+
+    // Low 64 bits of the first hash point come from the key's plain hash.
+    assert_eq!(hashes[0] as u64, 10179864958193109059);
+}
+
+#[test]
+fn xor_fold_truncation() {
+    use {hash_iter::TruncationMode, std::hash::BuildHasher, xxhash_rust::xxh3::Xxh3Builder};
+
+    let hash1 = Xxh3Builder::new().with_seed(12345).hash_one("hello");
+
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_truncation_mode(TruncationMode::XorFold)
+        .build_hash_iter_hasher();
+    let first = hasher.hash_iter(&"hello", 1).next().unwrap();
+    assert_eq!(first, ((hash1 ^ (hash1 >> 32)) as u32) % u32::MAX);
+
+    let hasher = DoubleHashBuilder::<u16>::new()
+        .with_truncation_mode(TruncationMode::XorFold)
+        .build_hash_iter_hasher();
+    let first = hasher.hash_iter(&"hello", 1).next().unwrap();
+    let folded = hash1 ^ (hash1 >> 16) ^ (hash1 >> 32) ^ (hash1 >> 48);
+    assert_eq!(first, (folded as u16) % u16::MAX);
+
+    // Default mode keeps the low bits.
+    let hasher = DoubleHashBuilder::<u32>::new().build_hash_iter_hasher();
+    let first = hasher.hash_iter(&"hello", 1).next().unwrap();
+    assert_eq!(first, (hash1 as u32) % u32::MAX);
+
+    // No-op for 64-bit types.
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_truncation_mode(TruncationMode::XorFold)
+        .build_hash_iter_hasher();
+    assert_eq!(hasher.hash_iter(&"hello", 1).next(), Some(hash1));
+}
+
+#[test]
+fn u64_count() {
+    let hasher = DoubleHashHasher::new();
+    assert!(hasher
+        .hash_iter_u64(&"hello", 100)
+        .eq(hasher.hash_iter(&"hello", 100)));
+
+    // Counts are not limited by the width of the output type.
+    let hasher = DoubleHashBuilder::<u8>::new().build_hash_iter_hasher();
+    assert_eq!(hasher.hash_iter_u64(&"hello", 1000).count(), 1000);
+    assert!(hasher
+        .hash_iter_u64(&"hello", 1000)
+        .take(3)
+        .eq([67, 181, 41]));
+}
+
+#[test]
+fn hash_at() {
+    let hasher = DoubleHashHasher::new();
+    let hashes = hasher.hash_iter(&"hello", 100).collect::<Vec<_>>();
+    for (i, &hash) in hashes.iter().enumerate() {
+        assert_eq!(hasher.hash_at(&"hello", i as u64), hash);
+    }
+
+    // Random access on the iterator does not depend on its position.
+    let mut iter = Hashes::new(12345u32, 67890, 1000, 10);
+    let first = iter.next().unwrap();
+    assert_eq!(iter.hash_at(0), first);
+    assert_eq!(iter.hash_at(5), iter.clone().nth(4).unwrap());
+
+    // A fresh iterator has its start points already reduced.
+    let iter = Hashes::new(12345u32, 67890, 1000, 10);
+    assert_eq!((iter.hash1(), iter.hash2()), (345, 890));
+    assert_eq!(iter.hash_at(0), 345);
+    assert_eq!(iter.clone().next_back(), Some(iter.hash_at(9)));
+    assert!(iter.rev().eq((0..10)
+        .rev()
+        .map(|i| Hashes::new(345u32, 890, 1000, 10).hash_at(i))));
+
+    // The last index does not overflow, including for the full range.
+    assert!(hasher.hashes(&"x", 3).hash_at(u64::MAX) < usize::MAX as u64);
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_full_range()
+        .build_hash_iter_hasher();
+    hasher.hashes(&"x", 3).hash_at(u64::MAX);
+    assert!(hasher.hash_at(&"x", u64::MAX - 1) != hasher.hash_at(&"x", u64::MAX));
+}
+
+#[test]
+fn unchecked() {
+    let iter = unsafe { Hashes::new_unchecked(345u32, 890, 1000, 10) };
+    assert!(iter.eq(Hashes::new(12345u32, 67890, 1000, 10)));
+
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    for key in 0..100 {
+        let unchecked = unsafe { hasher.hash_iter_unchecked(&key, 10) };
+        assert!(unchecked.eq(hasher.hash_iter(&key, 10)));
+    }
+}
+
+#[test]
+fn internal_iteration() {
+    let mut iter = Hashes::new(12345u64, 67890, 1000, 10);
+    iter.next();
+    iter.next_back();
+    let expected = iter.clone().collect::<Vec<_>>();
+
+    let mut visited = Vec::new();
+    iter.clone().for_each(|hash| visited.push(hash));
+    assert_eq!(visited, expected);
+
+    let folded = iter.clone().fold(Vec::new(), |mut acc, hash| {
+        acc.push(hash);
+        acc
+    });
+    assert_eq!(folded, expected);
+
+    // Short-circuiting stops right after the found hash point.
+    let pos = Iterator::position(&mut iter.clone(), |hash| hash == expected[3]);
+    assert_eq!(pos, Some(3));
+    let mut rest = iter.clone();
+    assert_eq!(rest.find(|&hash| hash == expected[3]), Some(expected[3]));
+    assert!(rest.eq(expected[4..].iter().copied()));
+}
+
+#[test]
+fn fused() {
+    fn assert_fused<I: std::iter::FusedIterator>(_: &I) {}
+
+    let mut iter = Hashes::new(12345u64, 67890, 1000, 3);
+    assert_fused(&iter);
+    assert_eq!(iter.by_ref().count(), 3);
+    for _ in 0..3 {
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+}
+
+#[test]
+fn size_hint() {
+    let mut iter = Hashes::new(12345u64, 67890, 1000, 10);
+    assert_eq!(iter.size_hint(), (10, Some(10)));
+    iter.next();
+    iter.next_back();
+    assert_eq!(iter.size_hint(), (8, Some(8)));
+    iter.nth(5);
+    assert_eq!(iter.size_hint(), (2, Some(2)));
+    assert_eq!(iter.len(), 2);
+    iter.by_ref().for_each(drop);
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+
+    // Counts beyond `usize::MAX` saturate, rather than truncate.
+    let iter = Hashes::new_u64(1u128, 2, u128::MAX, u64::MAX);
+    if usize::BITS < u64::BITS {
+        assert_eq!(iter.size_hint(), (usize::MAX, None));
+    } else {
+        assert_eq!(
+            iter.size_hint(),
+            (u64::MAX as usize, Some(u64::MAX as usize))
+        );
+    }
+
+    // Counts of the output type are the same as 64-bit ones, and are capped
+    // past `u64::MAX`.
+    assert!(Hashes::new(123u8, 67, 200, 100).eq(Hashes::new_u64(123u8, 67, 200, 100)));
+    assert_eq!(Hashes::new(1u128, 2, u128::MAX, u128::MAX).k(), u64::MAX);
+}
+
+#[test]
+#[should_panic(expected = "k must not be negative")]
+fn negative_count() {
+    let _ = Hashes::new(1i64, 2, 1000, -1);
+}
+
+#[test]
+fn with_start() {
+    let hasher = DoubleHashHasher::new();
+    let hashes = hasher.hash_iter(&"hello", 100).collect::<Vec<_>>();
+
+    for start in [0, 1, 2, 50, 99] {
+        let resumed = hasher.hashes(&"hello", 100).with_start(start);
+        assert!(resumed.eq(hashes[start as usize..].iter().copied()));
+    }
+    assert_eq!(hasher.hashes(&"hello", 100).with_start(100).next(), None);
+    assert_eq!(hasher.hashes(&"hello", 100).with_start(1000).next(), None);
+
+    // Restarting a partially consumed iterator.
+    let mut iter = hasher.hashes(&"hello", 100);
+    iter.nth(10);
+    assert!(iter.with_start(0).eq(hashes.iter().copied()));
+}
+
+#[test]
+fn state_accessors() {
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    let mut iter = hasher.hashes(&"hello", 10);
+    assert_eq!((iter.n(), iter.k()), (1000, 10));
+    assert_eq!((iter.position(), iter.remaining()), (0, 10));
+    assert!(iter.hash1() < 1000 && iter.hash2() < 1000);
+
+    iter.nth(2);
+    iter.next_back();
+    assert_eq!((iter.position(), iter.remaining(), iter.k()), (3, 6, 9));
+
+    // Checkpoint and resume.
+    let resumed =
+        Hashes::new_u64(iter.hash1(), iter.hash2(), iter.n(), iter.k()).with_start(iter.position());
+    assert!(resumed.eq(iter));
+}
+
+#[test]
+fn split_at() {
+    let hasher = DoubleHashHasher::new();
+    let hashes = hasher.hash_iter(&"hello", 100).collect::<Vec<_>>();
+
+    let mut iter = hasher.hashes(&"hello", 100);
+    iter.nth(9);
+    let (head, tail) = iter.split_at(40);
+    assert_eq!(head.remaining(), 40);
+    let (head, tail) = std::thread::scope(|s| {
+        let head = s.spawn(|| head.collect::<Vec<_>>());
+        let tail = s.spawn(|| tail.collect::<Vec<_>>());
+        (head.join().unwrap(), tail.join().unwrap())
+    });
+    assert_eq!(head, hashes[10..50]);
+    assert_eq!(tail, hashes[50..]);
+
+    let (head, tail) = hasher.hashes(&"hello", 100).split_at(1000);
+    assert!(head.eq(hashes.iter().copied()));
+    assert_eq!(tail.count(), 0);
+}
+
+#[test]
+fn chunks() {
+    let hasher = DoubleHashHasher::new();
+    let hashes = hasher.hash_iter(&"hello", 100).collect::<Vec<_>>();
+
+    let chunks = hasher.hashes(&"hello", 100).chunks(30).collect::<Vec<_>>();
+    assert_eq!(
+        chunks.iter().map(|c| c.remaining()).collect::<Vec<_>>(),
+        vec![30, 30, 30, 10]
+    );
+    assert!(chunks.into_iter().flatten().eq(hashes.iter().copied()));
+}
+
+#[test]
+fn hash_iter_into() {
+    let hasher = DoubleHashHasher::new();
+    let hashes = hasher.hash_iter(&"hello", 10).collect::<Vec<_>>();
+
+    let mut buf = [0; 10];
+    assert_eq!(hasher.hash_iter_into(&"hello", &mut buf), 10);
+    assert_eq!(buf[..], hashes[..]);
+
+    // Filling from a partially consumed iterator, into a larger buffer.
+    let mut iter = hasher.hashes(&"hello", 10);
+    iter.nth(2);
+    let mut buf = [0; 16];
+    assert_eq!(iter.fill(&mut buf), 7);
+    assert_eq!(buf[..7], hashes[3..]);
+    assert_eq!(iter.next(), None);
+
+    let mut buf = [0; 4];
+    let mut iter = hasher.hashes(&"hello", 10);
+    assert_eq!(iter.fill(&mut buf), 4);
+    assert_eq!(buf[..], hashes[..4]);
+    assert!(iter.eq(hashes[4..].iter().copied()));
+}
+
+#[test]
+fn hash_array() {
+    let hasher = DoubleHashHasher::new();
+    let hashes = hasher.hash_iter(&"hello", 7).collect::<Vec<_>>();
+
+    let array: [u64; 7] = hasher.hash_array(&"hello");
+    assert_eq!(array[..], hashes[..]);
+    assert_eq!(hasher.hash_array::<0, _>(&"hello"), []);
+
+    let hasher = DoubleHashBuilder::<u16>::new()
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    let array = hasher.hash_array::<3, _>(&"hello");
+    assert_eq!(array, [115, 629, 144]);
+}
+
+#[test]
+fn hash_for_each() {
+    let hasher = DoubleHashHasher::new();
+    let hashes = hasher.hash_iter(&"hello", 10).collect::<Vec<_>>();
+
+    let mut visited = Vec::new();
+    hasher.hash_for_each(&"hello", 10, |hash| visited.push(hash));
+    assert_eq!(visited, hashes);
+
+    let mut bits = [false; 1000];
+    let hasher = DoubleHashBuilder::<usize>::new()
+        .with_n(bits.len())
+        .build_hash_iter_hasher();
+    hasher.hash_for_each(&"hello", 7, |idx| bits[idx] = true);
+    assert!(hasher.hash_iter(&"hello", 7).all(|idx| bits[idx]));
+}
+
+#[test]
+fn hash_iter_enumerated() {
+    let hasher = DoubleHashHasher::new();
+    let hashes = hasher.hash_iter(&"hello", 10).collect::<Vec<_>>();
+
+    for (i, hash) in hasher.hash_iter_enumerated(&"hello", 10) {
+        assert_eq!(hash, hashes[i as usize]);
+    }
+    assert_eq!(
+        hasher
+            .hash_iter_enumerated(&"hello", 10)
+            .last()
+            .map(|(i, _)| i),
+        Some(9)
+    );
+}
+
+#[test]
+fn hash_iter_distinct() {
+    use std::collections::BTreeSet;
+
+    // Small table, where duplicates are frequent.
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(16)
+        .build_hash_iter_hasher();
+    for key in 0..100 {
+        let hashes = hasher.hash_iter_distinct(&key, 10).collect::<Vec<_>>();
+        let unique = hashes.iter().collect::<BTreeSet<_>>();
+        assert_eq!((hashes.len(), unique.len()), (10, 10));
+        assert!(hashes.iter().all(|&h| h < 16));
+
+        // All the slots can be requested.
+        let all = hasher.hash_iter_distinct(&key, 16).collect::<BTreeSet<_>>();
+        assert_eq!(all, (0..16).collect());
+    }
+
+    // Without duplicates, the sequence is the same as the regular one.
+    let hasher = DoubleHashHasher::new();
+    assert!(hasher
+        .hash_iter_distinct(&"hello", 10)
+        .eq(hasher.hash_iter(&"hello", 10)));
+}
+
+#[test]
+#[should_panic(expected = "count must not exceed n")]
+fn hash_iter_distinct_count_exceeds_n() {
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(16)
+        .build_hash_iter_hasher();
+    let _ = hasher.hash_iter_distinct(&"hello", 17);
+}
+
+#[test]
+fn hash_iter_distinct_full_range() {
+    use std::collections::BTreeSet;
+
+    let hasher = DoubleHashBuilder::<u8>::new()
+        .with_full_range()
+        .build_hash_iter_hasher();
+    for key in 0..10 {
+        let hashes = hasher.hash_iter_distinct(&key, 100).collect::<Vec<_>>();
+        assert_eq!(hashes.iter().collect::<BTreeSet<_>>().len(), 100);
+
+        // All the slots of the type can be requested.
+        let all = hasher
+            .hash_iter_distinct(&key, 256)
+            .collect::<BTreeSet<_>>();
+        assert_eq!(all, (0..=255).collect());
+    }
+
+    // Distinct mode is not a no-op.
+    let hasher = DoubleHashBuilder::<u8>::new()
+        .with_full_range()
+        .with_distinct(true)
+        .build_hash_iter_hasher();
+    for key in 0..10 {
+        let hashes = hasher.hash_iter(&key, 256).collect::<BTreeSet<_>>();
+        assert_eq!(hashes.len(), 256);
+    }
+}
+
+#[test]
+#[should_panic(expected = "count must not exceed n")]
+fn hash_iter_distinct_full_range_count_exceeds_n() {
+    let hasher = DoubleHashBuilder::<u8>::new()
+        .with_full_range()
+        .build_hash_iter_hasher();
+    let _ = hasher.hash_iter_distinct(&"hello", 257);
+}
+
+#[test]
+fn hash_iter_range() {
+    let hasher = DoubleHashHasher::new();
+    let shifted = DoubleHashBuilder::new()
+        .with_n(4096 - 1024)
+        .build_hash_iter_hasher();
+    for key in 0..100 {
+        let hashes = hasher
+            .hash_iter_range(&key, 10, 1024..4096)
+            .collect::<Vec<_>>();
+        assert_eq!(hashes.len(), 10);
+        assert!(hashes.iter().all(|h| (1024..4096).contains(h)));
+
+        // Same as the plain sequence over `[0, hi - lo)`, offset by `lo`.
+        let expected = shifted.hash_iter(&key, 10).map(|h| h + 1024);
+        assert!(hashes.into_iter().eq(expected));
+    }
+
+    // Negative ranges are supported for signed types.
+    let hasher = DoubleHashBuilder::<i64>::new().build_hash_iter_hasher();
+    assert!(hasher
+        .hash_iter_range(&"hello", 100, -5..5)
+        .all(|h| (-5..5).contains(&h)));
+}
+
+#[test]
+#[should_panic(expected = "range must not be empty")]
+fn hash_iter_range_empty() {
+    let _ = DoubleHashHasher::new().hash_iter_range(&"hello", 10, 5..5);
+}
+
+#[test]
+fn hash_iter_f64() {
+    let hasher = DoubleHashHasher::new();
+    let floats = hasher.hash_iter_f64(&"hello", 1000).collect::<Vec<_>>();
+    assert_eq!(floats.len(), 1000);
+    assert!(floats.iter().all(|f| (0.0..1.0).contains(f)));
+
+    // Deterministic, and roughly uniform.
+    assert!(floats
+        .iter()
+        .copied()
+        .eq(hasher.hash_iter_f64(&"hello", 1000)));
+    let mean = floats.iter().sum::<f64>() / floats.len() as f64;
+    assert!((mean - 0.5).abs() < 0.05, "mean: {mean}");
+}
+
+#[test]
+fn hash_iter_with_salt() {
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    let hashes = |salt| {
+        hasher
+            .hash_iter_with_salt("hello", salt, 10)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(hashes(1), hashes(1));
+    assert_ne!(hashes(1), hashes(2));
+    assert!(hashes(1)
+        .into_iter()
+        .eq(hasher.hash_iter(&(1u64, "hello"), 10)));
+
+    // Distinct hash values are kept distinct.
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_n(10)
+        .with_distinct(true)
+        .build_hash_iter_hasher();
+    let mut hashes = hasher
+        .hash_iter_with_salt("hello", 1, 10)
+        .collect::<Vec<_>>();
+    hashes.sort();
+    assert_eq!(hashes, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn domain_separation() {
+    let hasher = |domain| {
+        DoubleHashBuilder::<u64>::new()
+            .with_n(1000)
+            .with_domain(domain)
+            .build_hash_iter_hasher()
+    };
+    let hashes = |domain| hasher(domain).hash_iter(&42u64, 10).collect::<Vec<_>>();
+    assert_eq!(hashes("user-ids"), hashes("user-ids"));
+    assert_ne!(hashes("user-ids"), hashes("session-ids"));
+    assert!(!hasher("user-ids")
+        .hash_iter(&42u64, 10)
+        .eq(DoubleHashHasher::with_seeds_and_n(12345, 67890, 1000u64).hash_iter(&42u64, 10)));
+
+    // Same as setting the domain on the hasher.
+    assert!(hasher("user-ids")
+        .hash_iter(&42u64, 10)
+        .eq(DoubleHashHasher::with_seeds_and_n(12345, 67890, 1000u64)
+            .with_domain("user-ids")
+            .hash_iter(&42u64, 10)));
+
+    // Other key hashing strategies are separated too.
+    for key_hashing in [
+        KeyHashing::Single128 { seed: 1 },
+        KeyHashing::Mixed,
+        KeyHashing::Integer { seed: 1 },
+    ] {
+        let hashes = |domain| {
+            DoubleHashHasher::new()
+                .with_key_hashing(key_hashing)
+                .with_domain(domain)
+                .hash_iter(&42u64, 10)
+                .collect::<Vec<_>>()
+        };
+        assert_ne!(hashes("user-ids"), hashes("session-ids"));
+    }
+}
+
+#[test]
+fn constant_time() {
+    fn check<T: hash_iter::Number>(n: T) {
+        for reduction in [Reduction::Modulo, Reduction::Lemire, Reduction::Auto] {
+            for nonzero_h2 in [false, true] {
+                let builder = DoubleHashBuilder::<T>::new()
+                    .with_n(n)
+                    .with_reduction(reduction)
+                    .with_nonzero_h2(nonzero_h2);
+                let hasher = builder.build_hash_iter_hasher();
+                let constant_time = builder.with_constant_time(true).build_hash_iter_hasher();
+                for key in 0..100 {
+                    assert!(
+                        constant_time
+                            .hash_iter(&key, 10)
+                            .eq(hasher.hash_iter(&key, 10)),
+                        "n: {n:?}, {reduction:?}, nonzero_h2: {nonzero_h2}"
+                    );
+                }
+            }
+        }
+    }
+
+    // Same sequences as in the default mode.
+    for n in [0, 1, 2, 3, 10, 1000, 1024, u64::MAX] {
+        check(n);
+    }
+    for n in [0, 1, 7, 1 << 31, u32::MAX] {
+        check(n);
+    }
+    for n in [0, 1, 255] {
+        check::<u8>(n);
+    }
+    for n in [0, 1, 1000, u128::MAX / 3] {
+        check::<u128>(n);
+    }
+}
+
+#[test]
+#[should_panic(expected = "distinct hashes cannot be generated in constant time")]
+fn constant_time_distinct() {
+    DoubleHashBuilder::<u64>::new()
+        .with_distinct(true)
+        .with_constant_time(true)
+        .build_hash_iter_hasher();
+}
+
+#[test]
+fn hash_iter_bytes() {
+    use xxhash_rust::xxh3::xxh3_64_with_seed;
+
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    let (h1, h2) = (
+        xxh3_64_with_seed(b"hello", 12345),
+        xxh3_64_with_seed(b"hello", 67890),
+    );
+    assert!(hasher
+        .hash_iter_bytes(b"hello", 10)
+        .eq(Hashes::new(h1, h2, 1000, 10)));
+    assert!(hasher
+        .hash_iter_bytes(b"hello", 10)
+        .eq(hasher.hash_iter(&RawBytes("hello"), 10)));
+
+    // Unlike the `Hash` implementation of byte slices.
+    assert!(!hasher
+        .hash_iter_bytes(b"hello", 10)
+        .eq(hasher.hash_iter(&b"hello"[..], 10)));
+}
+
+#[test]
+fn iter_from_hashes() {
+    use xxhash_rust::xxh3::xxh3_64_with_seed;
+
+    let (h1, h2) = (
+        xxh3_64_with_seed(b"hello", 12345),
+        xxh3_64_with_seed(b"hello", 67890),
+    );
+    let key = RawBytes("hello");
+
+    // Same as hashing the key with the hasher's configuration.
+    for builder in [
+        DoubleHashBuilder::<u64>::new().with_n(1000),
+        DoubleHashBuilder::<u64>::new()
+            .with_n(1000)
+            .with_reduction(Reduction::Lemire)
+            .with_nonzero_h2(true),
+        DoubleHashBuilder::<u64>::new()
+            .with_n(10)
+            .with_distinct(true),
+        DoubleHashBuilder::<u64>::new()
+            .with_n(1000)
+            .with_constant_time(true),
+    ] {
+        let hasher = builder.build_hash_iter_hasher();
+        assert!(hasher
+            .iter_from_hashes(h1, h2, 10)
+            .eq(hasher.hash_iter(&key, 10)));
+    }
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    assert!(hasher
+        .iter_from_hashes(h1, h2, 10)
+        .eq(hasher.hash_iter(&key, 10)));
+    assert!(DoubleHashHasher::<u64>::new()
+        .iter_from_hashes(h1, h2, 10)
+        .eq(Hashes::new(h1, h2, u64::MAX, 10)));
+
+    // Wider output types take the base hashes as they are.
+    let hasher = DoubleHashBuilder::<u128>::new()
+        .with_n(1 << 100)
+        .build_hash_iter_hasher();
+    assert!(hasher.iter_from_hashes(h1, h2, 10).eq(Hashes::new(
+        h1 as u128,
+        h2 as u128,
+        1 << 100,
+        10
+    )));
+}
+
+#[test]
+fn hash_iter_prehashed() {
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    let hashes = |hasher: &DoubleHashHasher<u64>, hash| {
+        hasher.hash_iter_prehashed(hash, 10).collect::<Vec<_>>()
+    };
+    assert_eq!(hashes(&hasher, 42), hashes(&hasher, 42));
+    assert_ne!(hashes(&hasher, 42), hashes(&hasher, 43));
+    assert!(hashes(&hasher, 42).iter().all(|&h| h < 1000));
+
+    // Both base hashes are mixed out of the key hash, with SplitMix64.
+    fn splitmix64(seed: u64, i: u64) -> u64 {
+        let mut z = seed.wrapping_add((i + 1).wrapping_mul(0x9e3779b97f4a7c15));
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+    assert!(hasher
+        .hash_iter_prehashed(42, 10)
+        .eq(hasher.iter_from_hashes(splitmix64(42, 0), splitmix64(42, 1), 10)));
+
+    // Domains are separated.
+    let other = DoubleHashBuilder::<u64>::new()
+        .with_n(1000)
+        .with_domain("user-ids")
+        .build_hash_iter_hasher();
+    assert_ne!(hashes(&hasher, 42), hashes(&other, 42));
+}
+
+#[test]
+fn hash_iter_portable() {
+    use xxhash_rust::xxh3::xxh3_64_with_seed;
+
+    fn encoding<K: PortableKey + ?Sized>(key: &K) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        key.encode(&mut bytes);
+        bytes
+    }
+
+    assert_eq!(encoding(&0x0102u16), [2, 1]);
+    assert_eq!(encoding(&-1i8), [0xff]);
+    assert_eq!(encoding(&1usize), encoding(&1u64));
+    assert_eq!(encoding(&-1isize), encoding(&-1i64));
+    assert_eq!(encoding(&true), [1]);
+    assert_eq!(encoding(&'a'), encoding(&97u32));
+    assert_eq!(encoding(&1.5f64), encoding(&1.5f64.to_bits()));
+    assert_eq!(encoding("ab"), [2, 0, 0, 0, 0, 0, 0, 0, b'a', b'b']);
+    assert_eq!(encoding(&"ab".to_string()), encoding("ab"));
+    assert_eq!(encoding(&b"ab"[..]), encoding("ab"));
+    assert_eq!(encoding(&vec![1u16, 2]), encoding(&(2u64, 1u16, 2u16)));
+    assert_eq!(encoding(&[1u16, 2]), encoding(&(1u16, 2u16)));
+    assert_eq!(encoding(&None::<u8>), [0]);
+    assert_eq!(encoding(&Some(7u8)), [1, 7]);
+    assert_eq!(encoding(&(1u8, "a", Some(true))), [
+        1, 1, 0, 0, 0, 0, 0, 0, 0, b'a', 1, 1
+    ]);
+
+    // The encoding is hashed as is.
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    let key = (42u32, "alice");
+    let bytes = encoding(&key);
+    let (h1, h2) = (
+        xxh3_64_with_seed(&bytes, 12345),
+        xxh3_64_with_seed(&bytes, 67890),
+    );
+    assert!(hasher
+        .hash_iter_portable(&key, 10)
+        .eq(Hashes::new(h1, h2, 1000, 10)));
+    assert!(hasher
+        .hash_iter_portable(&key, 10)
+        .eq(hasher.hash_iter_bytes(&bytes, 10)));
+}
+
+#[test]
+fn stream_hasher() {
+    fn check<T: hash_iter::Number, H1, H2>(hasher: &DoubleHashHasher<T, H1, H2>, key: &[u8])
+    where
+        H1: std::hash::BuildHasher,
+        H2: std::hash::BuildHasher,
+        H1::Hasher: Clone,
+        H2::Hasher: Clone,
+    {
+        for parts in [1, 3, 64, 241] {
+            let mut stream = hasher.stream_hasher();
+            for part in key.chunks(parts) {
+                stream.write(part);
+            }
+            assert!(stream.finish_iter(10).eq(hasher.hash_iter_bytes(key, 10)));
+        }
+    }
+
+    // Long enough for both the short and the streaming paths of XXH3.
+    let key = (0..1000).map(|i| i as u8).collect::<Vec<_>>();
+    for len in [0, 5, 240, 1000] {
+        let key = &key[..len];
+        check(&DoubleHashHasher::<u64>::new(), key);
+        for key_hashing in [KeyHashing::Single128 { seed: 1 }, KeyHashing::Mixed] {
+            check(
+                &DoubleHashHasher::<u64>::new().with_key_hashing(key_hashing),
+                key,
+            );
+        }
+        check(&DoubleHashHasher::<u64>::new().with_domain("user-ids"), key);
+        check(&DoubleHashHasher::<u64>::new().with_distinct(true), key);
+        check(
+            &DoubleHashBuilder::<u128>::new()
+                .with_n(1 << 100)
+                .build_hash_iter_hasher(),
+            key,
+        );
+    }
+
+    // Integer keys are split along words.
+    let hasher = DoubleHashHasher::<u64>::new()
+        .with_key_hashing(KeyHashing::Integer { seed: 1 })
+        .with_domain("user-ids");
+    let mut stream = hasher.stream_hasher();
+    stream.write(&1u64.to_le_bytes());
+    stream.write(&2u64.to_le_bytes());
+    assert!(stream
+        .finish_iter(10)
+        .eq(hasher.hash_iter(&(1u64, 2u64), 10)));
+}
+
+#[test]
+fn hash_iter_batch() {
+    let hasher = DoubleHashHasher::new();
+    let keys = ["hello", "world", "foo", "bar"];
+    let mut out = vec![0; keys.len() * 3];
+    hasher.hash_iter_batch(&keys, 3, &mut out);
+    for (key, row) in keys.iter().zip(out.chunks(3)) {
+        assert!(row.iter().copied().eq(hasher.hash_iter(key, 3)));
+    }
+
+    // Nothing to write.
+    hasher.hash_iter_batch(&keys, 0, &mut []);
+    hasher.hash_iter_batch::<&str>(&[], 3, &mut []);
+}
+
+#[test]
+#[should_panic(expected = "out must hold count hashes per key")]
+fn hash_iter_batch_wrong_len() {
+    let mut out = [0; 5];
+    DoubleHashHasher::new().hash_iter_batch(&["hello", "world"], 3, &mut out);
+}
+
+#[test]
+fn hash_iter_batch_lanes() {
+    fn check<T: hash_iter::Number + Default + PartialEq>(hasher: DoubleHashHasher<T>) {
+        // Number of keys not a multiple of lane count, to cover the tail.
+        let keys = (0..103).collect::<Vec<u32>>();
+        for count in [0, 1, 7, 100] {
+            let mut expected = vec![T::default(); keys.len() * count];
+            for (key, row) in keys.iter().zip(expected.chunks_exact_mut(count.max(1))) {
+                hasher.hash_iter_into(key, row);
+            }
+            let mut out = vec![T::default(); keys.len() * count];
+            hasher.hash_iter_batch(&keys, count, &mut out);
+            assert!(out == expected);
+        }
+    }
+
+    check(DoubleHashHasher::new());
+    check(
+        DoubleHashBuilder::<u64>::new()
+            .with_n(10)
+            .build_hash_iter_hasher(),
+    );
+    check(
+        DoubleHashBuilder::<u64>::new()
+            .with_n(u64::MAX)
+            .build_hash_iter_hasher(),
+    );
+    check(
+        DoubleHashBuilder::<u64>::new()
+            .with_full_range()
+            .build_hash_iter_hasher(),
+    );
+    check(DoubleHashBuilder::<usize>::new().build_hash_iter_hasher());
+    check(
+        DoubleHashBuilder::<i64>::new()
+            .with_n(1 << 40)
+            .build_hash_iter_hasher(),
+    );
+    check(DoubleHashHasher::new().with_hashing_mode(HashingMode::Plain));
+    check(DoubleHashHasher::new().with_hashing_mode(HashingMode::Linear { stride: 3 }));
+    check(
+        DoubleHashBuilder::<u64>::new()
+            .with_distinct(true)
+            .with_n(10)
+            .build_hash_iter_hasher(),
+    );
+    // Not computed in 64 bits.
+    check(
+        DoubleHashBuilder::<u32>::new()
+            .with_n(1000)
+            .build_hash_iter_hasher(),
+    );
+}
+
+#[test]
+fn multi_hashes() {
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    let keys = ["hello", "world", "foo", "bar"];
+    let multi = hasher.multi_hashes(&keys, 10);
+    assert_eq!(multi.len(), 10);
+
+    let steps = multi.collect::<Vec<_>>();
+    assert_eq!(steps.len(), 10);
+    for (lane, key) in keys.iter().enumerate() {
+        assert!(steps
+            .iter()
+            .map(|step| step[lane])
+            .eq(hasher.hash_iter(key, 10)));
+    }
+
+    assert_eq!(hasher.multi_hashes(&keys, 0).next(), None);
+}
+
+#[test]
+fn prefetch_hashes() {
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(1024)
+        .build_hash_iter_hasher();
+
+    // Indices are the same, whether the table covers them or not.
+    for table in [vec![0u64; 1024], vec![0u64; 10], vec![]] {
+        assert!(hasher
+            .prefetch_hashes(&"hello", 7, &table)
+            .eq(hasher.hash_iter(&"hello", 7)));
+    }
+}
+
+#[test]
+fn probe_sequence() {
+    use {
+        hash_iter::ProbeSequence,
+        std::{collections::BTreeSet, hash::BuildHasher},
+        xxhash_rust::xxh3::Xxh3Builder,
+    };
+
+    let hasher = DoubleHashHasher::new();
+    for key in 0..100u32 {
+        // Every slot is probed exactly once.
+        for capacity in [1, 2, 64, 1024] {
+            let slots = hasher.probe_sequence(&key, capacity, 1).collect::<Vec<_>>();
+            assert_eq!(slots.len(), capacity);
+            assert_eq!(slots.iter().collect::<BTreeSet<_>>().len(), capacity);
+        }
+
+        // Every slot is covered by exactly one group.
+        let mut seen = [0; 64];
+        for pos in hasher.probe_sequence(&key, 64, 16) {
+            (0..16).for_each(|j| seen[(pos + j) % 64] += 1);
+        }
+        assert_eq!(seen, [1; 64]);
+
+        // Positions follow the double hashing sequence of the key.
+        let h1 = Xxh3Builder::new().with_seed(12345).hash_one(key);
+        let h2 = Xxh3Builder::new().with_seed(67890).hash_one(key);
+        assert!(hasher
+            .probe_sequence(&key, 64, 4)
+            .eq(ProbeSequence::new(h1, h2, 64, 4)));
+    }
+
+    // The first position is the first hash, modulo capacity, and the step is
+    // the (odd) second hash, in groups.
+    let seq = ProbeSequence::new(35, 6, 32, 4);
+    assert_eq!(seq.len(), 8);
+    assert!(seq.eq([3, 31, 27, 23, 19, 15, 11, 7]));
+    assert!(ProbeSequence::new(35, 6, 32, 32).eq([3]));
+}
+
+#[test]
+#[should_panic(expected = "capacity must be a power of two")]
+fn probe_sequence_capacity_not_power_of_two() {
+    let _ = DoubleHashHasher::new().probe_sequence(&"hello", 100, 1);
+}
+
+#[test]
+fn hash_iter_chunks() {
+    let hasher = DoubleHashHasher::new();
+    let hashes = hasher.hash_iter(&"hello", 12).collect::<Vec<_>>();
+
+    let chunks = hasher
+        .hash_iter_chunks::<4, _>(&"hello", 3)
+        .collect::<Vec<_>>();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks.concat(), hashes);
+
+    assert_eq!(hasher.hash_iter_chunks::<4, _>(&"hello", 0).count(), 0);
+}
+
+#[test]
+fn caching_hash_iter_hasher() {
+    use {
+        hash_iter::CachingHashIterHasher,
+        std::{cell::Cell, hash::BuildHasher, rc::Rc},
+        xxhash_rust::xxh3::{Xxh3, Xxh3Builder},
+    };
+
+    // Counts how many times keys are hashed.
+    #[derive(Clone)]
+    struct Counting(Xxh3Builder, Rc<Cell<usize>>);
+
+    impl BuildHasher for Counting {
+        type Hasher = Xxh3;
+
+        fn build_hasher(&self) -> Xxh3 {
+            self.1.set(self.1.get() + 1);
+            self.0.build_hasher()
+        }
+    }
+
+    let calls = Rc::new(Cell::new(0));
+    let hasher = DoubleHashHasher::with_hash_builders(
+        Counting(Xxh3Builder::new().with_seed(1), calls.clone()),
+        Counting(Xxh3Builder::new().with_seed(2), calls.clone()),
+        1000u64,
+    );
+    let expected = hasher.hash_iter(&"hello", 7).collect::<Vec<_>>();
+    calls.set(0);
+
+    let caching = CachingHashIterHasher::new(hasher);
+    assert!(caching.hash_iter(&"hello", 7).eq(expected.iter().copied()));
+    assert!(caching.hash_iter(&"hello", 7).eq(expected.iter().copied()));
+    assert_eq!(calls.get(), 2);
+
+    // A different key evicts the cached one.
+    let _ = caching.hash_iter(&"world", 7);
+    assert!(caching.hash_iter(&"hello", 7).eq(expected.iter().copied()));
+    assert_eq!(calls.get(), 6);
+
+    caching.clear();
+    let _ = caching.hash_iter(&"hello", 7);
+    assert_eq!(calls.get(), 8);
+}
+
+#[test]
+fn plain_hashing_mode() {
+    let n = 1_000_003u64;
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_n(n)
+        .with_hashing_mode(HashingMode::Plain)
+        .build_hash_iter_hasher();
+
+    // Same start points as in the enhanced mode, without the cubic term.
+    let enhanced = DoubleHashBuilder::<u64>::new()
+        .with_n(n)
+        .build_hash_iter_hasher();
+    let hashes = enhanced.hashes(&"hello", 100);
+    let (h1, h2) = (hashes.hash1(), hashes.hash2());
+    let expected = (0..100).map(|i| (h1 + i * h2) % n).collect::<Vec<_>>();
+
+    assert_eq!(
+        hasher.hash_iter(&"hello", 100).collect::<Vec<_>>(),
+        expected
+    );
+    assert!(hasher
+        .hashes(&"hello", 100)
+        .rev()
+        .eq(expected.iter().rev().copied()));
+    assert_eq!(hasher.hash_at(&"hello", 42), expected[42]);
+    assert!(hasher
+        .hashes(&"hello", 100)
+        .with_start(50)
+        .eq(expected[50..].iter().copied()));
+
+    let steps = hasher.multi_hashes(&["hello"], 100).map(|[h]| h);
+    assert!(steps.eq(expected.iter().copied()));
+}
+
+#[test]
+fn hashes_with_hashing_mode() {
+    let (h1, h2, n) = (12345u64, 67890u64, 1_000_003u64);
+    let enhanced = Hashes::new(h1, h2, n, 100);
+    assert_eq!(enhanced.hashing_mode(), HashingMode::Enhanced);
+
+    let plain = enhanced.clone().with_hashing_mode(HashingMode::Plain);
+    assert_eq!(plain.hashing_mode(), HashingMode::Plain);
+    assert!(plain.clone().eq((0..100).map(|i| (h1 + i * h2) % n)));
+
+    // Switching mid-iteration continues the sequence of the new mode.
+    let mut switched = enhanced.clone();
+    switched.nth(9);
+    let switched = switched.with_hashing_mode(HashingMode::Plain);
+    assert!(switched.eq(plain.skip(10)));
+}
+
+#[test]
+fn probe_strategy() {
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(1009)
+        .build_hash_iter_hasher();
+
+    // Built-in modes match the regular sequences.
+    for mode in [HashingMode::Enhanced, HashingMode::Plain] {
+        let expected = hasher.with_hashing_mode(mode).hashes(&"hello", 50);
+        assert!(hasher.probes(&"hello", 50, mode).eq(expected));
+    }
+
+    // Custom formula, with the result reduced modulo `n`.
+    let hashes = hasher.hashes(&"hello", 0);
+    let (h1, h2) = (hashes.hash1(), hashes.hash2());
+    let probes = hasher.probes(&"hello", 50, |h1: u32, h2: u32, i, _| {
+        h1 + h2 * i as u32 * 3
+    });
+    assert_eq!(probes.len(), 50);
+    assert!(probes
+        .rev()
+        .eq((0..50).rev().map(|i| (h1 + h2 * i * 3) % 1009)));
+
+    // Full range: hash points are not reduced.
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_full_range()
+        .build_hash_iter_hasher();
+    for mode in [HashingMode::Enhanced, HashingMode::Plain] {
+        let expected = hasher.with_hashing_mode(mode).hashes(&"x", 3);
+        assert!(hasher.probes(&"x", 3, mode).eq(expected));
+    }
+    let probes = hasher.probes(&"x", 3, |h1: u64, h2: u64, i, _| {
+        h1.wrapping_add(h2.wrapping_mul(i))
+    });
+    assert_eq!(probes.count(), 3);
+}
+
+#[test]
+fn quadratic_hashes() {
+    use {hash_iter::QuadraticHashes, std::collections::BTreeSet};
+
+    let n = 1009u64;
+    let quadratic = QuadraticHashes::new(5u64, 3, 7, n, 100);
+    assert_eq!(quadratic.len(), 100);
+    assert!(quadratic.eq((0..100).map(|i| (5 + 3 * i + 7 * i * i) % n)));
+
+    // Constants are reduced modulo `n`, and no intermediate value overflows.
+    let max = u64::MAX;
+    let quadratic = QuadraticHashes::new(max - 1, max - 2, max - 3, max, 4);
+    assert!(quadratic.eq([max - 1, max - 6, max - 17, max - 34]));
+
+    // Triangular probing visits all the slots of a power-of-two table.
+    let triangular = QuadraticHashes::triangular(5u32, 64, 64);
+    assert_eq!(triangular.collect::<BTreeSet<_>>(), (0..64).collect());
+
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(64)
+        .build_hash_iter_hasher();
+    let h1 = hasher.hashes(&"hello", 0).hash1();
+    assert!(hasher
+        .triangular_hashes(&"hello", 10)
+        .eq((0..10).map(|i| (h1 + i * (i + 1) / 2) % 64)));
+    assert!(hasher
+        .quadratic_hashes(&"hello", 10, 1, 1)
+        .eq((0..10).map(|i| (h1 + i + i * i) % 64)));
+
+    // The full range wraps.
+    let quadratic = QuadraticHashes::new(max - 1, 3, 7, 0, 4);
+    assert!(quadratic.eq((0..4).map(|i| (max - 1).wrapping_add(3 * i + 7 * i * i))));
+    let triangular = QuadraticHashes::triangular(5u8, 0, 256);
+    assert_eq!(triangular.collect::<BTreeSet<_>>(), (0..=255).collect());
+
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_full_range()
+        .build_hash_iter_hasher();
+    let h1 = hasher.hashes(&"hello", 0).hash1();
+    assert!(hasher
+        .triangular_hashes(&"hello", 10)
+        .eq((0..10).map(|i| h1.wrapping_add(i * (i + 1) / 2))));
+    assert!(hasher
+        .quadratic_hashes(&"hello", 10, 1, 1)
+        .eq((0..10).map(|i| h1.wrapping_add(i + i * i))));
+}
+
+#[test]
+fn linear_hashing_mode() {
+    let n = 1009u32;
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(n)
+        .with_hashing_mode(HashingMode::Linear { stride: 3 })
+        .build_hash_iter_hasher();
+
+    let h1 = hasher.hashes(&"hello", 0).hash1();
+    let expected = (0..100).map(|i| (h1 + 3 * i) % n).collect::<Vec<_>>();
+    assert_eq!(
+        hasher.hash_iter(&"hello", 100).collect::<Vec<_>>(),
+        expected
+    );
+    assert!(hasher
+        .hashes(&"hello", 100)
+        .rev()
+        .eq(expected.iter().rev().copied()));
+    assert!(hasher
+        .multi_hashes(&["hello"], 100)
+        .map(|[h]| h)
+        .eq(expected.iter().copied()));
+
+    // The stride is reduced modulo `n`.
+    let hasher = hasher.with_hashing_mode(HashingMode::Linear {
+        stride: u64::from(n) + 1,
+    });
+    assert!(hasher
+        .hash_iter(&"hello", 100)
+        .eq((0..100).map(|i| (h1 + i) % n)));
+}
+
+#[test]
+fn single_128_key_hashing() {
+    use {std::hash::Hash, xxhash_rust::xxh3::Xxh3};
+
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_n(1_000_003)
+        .with_key_hashing(KeyHashing::Single128 { seed: 42 })
+        .build_hash_iter_hasher();
+
+    // Start points are the halves of a single 128-bit hash of the key.
+    let mut xxh3 = Xxh3::with_seed(42);
+    "hello".hash(&mut xxh3);
+    let digest = xxh3.digest128();
+    let hashes = hasher.hashes(&"hello", 10);
+    assert_eq!(hashes.hash1(), digest as u64 % 1_000_003);
+    assert_eq!(hashes.hash2(), (digest >> 64) as u64 % 1_000_003);
+
+    let expected = Hashes::new(digest as u64, (digest >> 64) as u64, 1_000_003, 10);
+    assert!(hasher.hash_iter(&"hello", 10).eq(expected));
+
+    // Wider types use more hash words.
+    let hasher = DoubleHashBuilder::<u128>::new()
+        .with_n(u128::MAX)
+        .with_key_hashing(KeyHashing::Single128 { seed: 42 })
+        .build_hash_iter_hasher();
+    let hashes = hasher.hashes(&"hello", 10);
+    assert_eq!(hashes.hash1() as u64, digest as u64);
+    assert_eq!(hashes.hash2() as u64, (digest >> 64) as u64);
+    assert!(hashes.hash1() > u64::MAX as u128);
+}
+
+#[test]
+fn mixed_key_hashing() {
+    use std::hash::BuildHasher;
+
+    fn splitmix64(mut z: u64) -> u64 {
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_n(u64::MAX)
+        .with_key_hashing(KeyHashing::Mixed)
+        .build_hash_iter_hasher();
+
+    // The first start point is unchanged, the second one is mixed out of it.
+    let h1 = xxhash_rust::xxh3::Xxh3Builder::new()
+        .with_seed(12345)
+        .hash_one("hello");
+    let hashes = hasher.hashes(&"hello", 10);
+    assert_eq!(hashes.hash1(), h1 % u64::MAX);
+    assert_eq!(hashes.hash2(), splitmix64(h1) % u64::MAX);
+    assert!(hasher
+        .hash_iter(&"hello", 10)
+        .eq(Hashes::new(h1, splitmix64(h1), u64::MAX, 10)));
+}
+
+#[test]
+fn integer_key_hashing() {
+    const GOLDEN_GAMMA: u64 = 0x9e3779b97f4a7c15;
+    fn splitmix64(mut z: u64) -> u64 {
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    let seed = 42;
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_n(u64::MAX)
+        .with_key_hashing(KeyHashing::Integer { seed })
+        .build_hash_iter_hasher();
+
+    // The value of the key is mixed directly, regardless of its width.
+    let key = 12345u64;
+    let h1 = splitmix64((key ^ seed).wrapping_add(GOLDEN_GAMMA));
+    let h2 = splitmix64((key ^ seed).wrapping_add(GOLDEN_GAMMA.wrapping_mul(2)));
+    let expected = Hashes::new(h1, h2, u64::MAX, 10);
+    assert!(hasher.hash_iter(&key, 10).eq(expected.clone()));
+    assert!(hasher.hash_iter(&(key as u32), 10).eq(expected.clone()));
+    assert!(hasher.hash_iter(&(key as i16), 10).eq(expected));
+
+    // Distinct integer keys have distinct first start points.
+    let mut firsts = (0..1000u64)
+        .map(|key| hasher.hashes(&key, 0).hash1())
+        .collect::<Vec<_>>();
+    firsts.sort();
+    firsts.dedup();
+    assert_eq!(firsts.len(), 1000);
+
+    // Other keys are supported too.
+    assert!(hasher
+        .hash_iter(&"hello", 10)
+        .ne(hasher.hash_iter(&"world", 10)));
+}
+
+#[test]
+fn nonzero_h2() {
+    // Small `n`, so that `h2 = 0 (mod n)` happens for some keys.
+    for n in [2u32, 3, 8, 10, 64] {
+        let hasher = DoubleHashBuilder::<u32>::safe()
+            .with_n(n)
+            .build_hash_iter_hasher();
+        for key in 0..100 {
+            let h2 = hasher.hashes(&key, 0).hash2();
+            assert!((1..n).contains(&h2), "n: {n}, h2: {h2}");
+            if n.is_power_of_two() {
+                assert_eq!(h2 % 2, 1);
+            }
+        }
+    }
+
+    // With odd `h2`, plain double hashing visits all the slots.
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(64)
+        .with_hashing_mode(HashingMode::Plain)
+        .with_nonzero_h2(true)
+        .build_hash_iter_hasher();
+    for key in 0..100 {
+        let mut slots = hasher.hash_iter(&key, 64).collect::<Vec<_>>();
+        slots.sort();
+        assert!(slots.into_iter().eq(0..64));
+    }
+
+    // `n = 1` has a single slot anyway.
+    let hasher = DoubleHashBuilder::<u32>::safe()
+        .with_n(1)
+        .build_hash_iter_hasher();
+    assert!(hasher.hash_iter(&"hello", 3).eq([0, 0, 0]));
+}
+
+#[test]
+fn distinct_mode() {
+    use std::collections::BTreeSet;
+
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(16)
+        .with_distinct(true)
+        .build_hash_iter_hasher();
+    for key in 0..100 {
+        let hashes = hasher.hash_iter(&key, 16).collect::<Vec<_>>();
+        assert_eq!(hashes.iter().collect::<BTreeSet<_>>().len(), 16);
+        assert!(hasher
+            .hash_iter_distinct(&key, 16)
+            .eq(hashes.iter().copied()));
+
+        // All the methods agree.
+        assert_eq!(hasher.hash_array::<16, _>(&key)[..], hashes[..]);
+        let mut visited = Vec::new();
+        hasher.hash_for_each(&key, 16, |h| visited.push(h));
+        assert_eq!(visited, hashes);
+
+        // Past `n`, values are no longer distinct, but are still emitted.
+        let hashes = hasher.hash_iter(&key, 20).collect::<Vec<_>>();
+        assert_eq!(hashes.len(), 20);
+        assert_eq!(hashes[..16].iter().collect::<BTreeSet<_>>().len(), 16);
+    }
+}
+
+#[test]
+fn feistel_permutation() {
+    use std::collections::BTreeSet;
+
+    let hasher = DoubleHashHasher::new();
+    for bits in [0, 1, 2, 7, 10] {
+        let perm = hasher.feistel_permutation(&"hello", bits);
+        assert_eq!(perm.bits(), bits);
+
+        // Visits every index exactly once, and can be reversed.
+        let indices = perm.iter(u64::MAX).collect::<Vec<_>>();
+        assert_eq!(indices.len(), 1 << bits);
+        assert_eq!(indices.iter().collect::<BTreeSet<_>>().len(), 1 << bits);
+        for (x, &y) in indices.iter().enumerate() {
+            assert_eq!(perm.invert(y), x as u64);
+        }
+    }
+
+    // Full-width domain.
+    let perm = FeistelPermutation::new(64, 1, 2);
+    for x in [0, 1, u64::MAX, 1 << 63] {
+        assert_eq!(perm.invert(perm.permute(x)), x);
+    }
+
+    // Different keys, different permutations.
+    let other = hasher.feistel_permutation(&"world", 10);
+    let perm = hasher.feistel_permutation(&"hello", 10);
+    assert!(!perm.iter(10).eq(other.iter(10)));
+}
+
+#[test]
+#[should_panic(expected = "value is outside of the domain")]
+fn feistel_permutation_out_of_domain() {
+    FeistelPermutation::new(10, 1, 2).permute(1 << 10);
+}
+
+#[test]
+fn blocked_hashes() {
+    // 512-bit blocks.
+    let n = 1 << 20;
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(n)
+        .build_hash_iter_hasher();
+    for key in 0..100 {
+        let hashes = hasher.blocked_hashes(&key, 7, 512).collect::<Vec<_>>();
+        assert_eq!(hashes.len(), 7);
+
+        // The first value is the regular first hash, and selects the block.
+        assert_eq!(hashes[0], hasher.hashes(&key, 0).hash1());
+        let block = hashes[0] / 512;
+        assert!(hashes.iter().all(|h| h / 512 == block));
+    }
+
+    // Trailing slots (past the last full block) are never used.
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    for key in 0..100 {
+        assert!(hasher.blocked_hashes(&key, 7, 300).all(|h| h < 900));
+    }
+
+    // Full range, with and without trailing slots.
+    let hasher = DoubleHashBuilder::<u8>::new()
+        .with_full_range()
+        .build_hash_iter_hasher();
+    for key in 0..100 {
+        let hashes = hasher.blocked_hashes(&key, 7, 64).collect::<Vec<_>>();
+        assert_eq!(hashes[0], hasher.hashes(&key, 0).hash1());
+        assert!(hashes.iter().all(|h| h / 64 == hashes[0] / 64));
+
+        let hashes = hasher.blocked_hashes(&key, 7, 48).collect::<Vec<_>>();
+        assert!(hashes.iter().all(|&h| h < 240 && h / 48 == hashes[0] / 48));
+    }
+}
+
+#[test]
+fn hash_iter_pairs() {
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(1 << 20)
+        .build_hash_iter_hasher();
+    let (buckets, slots) = (1000, 16);
+    for key in 0..100 {
+        let pairs = hasher
+            .hash_iter_pairs(&key, 5, buckets, slots)
+            .collect::<Vec<_>>();
+        assert_eq!(pairs.len(), 5);
+        assert!(pairs.iter().all(|&(b, s)| b < buckets && s < slots));
+
+        // Pairs are the regular sequence modulo `n_buckets * n_slots`, split.
+        let flat = DoubleHashBuilder::<u32>::new()
+            .with_n(buckets * slots)
+            .build_hash_iter_hasher();
+        let expected = flat
+            .hash_iter(&key, 5)
+            .map(|h| (h / slots, h % slots))
+            .collect::<Vec<_>>();
+        assert_eq!(pairs, expected);
+    }
+}
+
+#[test]
+#[should_panic(expected = "too many slots in total")]
+fn hash_iter_pairs_overflow() {
+    let hasher = DoubleHashBuilder::<u32>::new().build_hash_iter_hasher();
+    let _ = hasher.hash_iter_pairs(&42, 5, 1 << 16, 1 << 16);
+}
+
+#[test]
+fn partitioned_hashes() {
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    for key in 0..100 {
+        // Equal segments: values are the regular sequence, reduced into each
+        // segment.
+        let hashes = hasher.partitioned_hashes(&key, 5).collect::<Vec<_>>();
+        let expected = hasher
+            .hash_iter(&key, 5)
+            .zip(0..)
+            .map(|(h, i)| i * 200 + h % 200)
+            .collect::<Vec<_>>();
+        assert_eq!(hashes, expected);
+
+        // Uneven segments.
+        let hashes = hasher.partitioned_hashes(&key, 7).collect::<Vec<_>>();
+        assert_eq!(hashes.len(), 7);
+        for (i, h) in (0..).zip(hashes) {
+            assert!((i * 1000 / 7..(i + 1) * 1000 / 7).contains(&h));
+        }
+    }
+
+    // As many segments as slots.
+    let hasher = DoubleHashBuilder::<u8>::new()
+        .with_n(255)
+        .build_hash_iter_hasher();
+    let hashes = hasher.partitioned_hashes(&42, 255).collect::<Vec<_>>();
+    assert_eq!(hashes, (0..=254).collect::<Vec<_>>());
+
+    // Full range.
+    let hasher = DoubleHashBuilder::<u8>::new()
+        .with_full_range()
+        .build_hash_iter_hasher();
+    for key in 0..100 {
+        let hashes = hasher.partitioned_hashes(&key, 4).collect::<Vec<_>>();
+        let expected = hasher
+            .hash_iter(&key, 4)
+            .zip(0..)
+            .map(|(h, i)| i * 64 + h % 64)
+            .collect::<Vec<_>>();
+        assert_eq!(hashes, expected);
+
+        assert!(hasher
+            .partitioned_hashes(&key, 1)
+            .eq(hasher.hash_iter(&key, 1)));
+        for (i, h) in (0..).zip(hasher.partitioned_hashes(&key, 3)) {
+            assert!((i * 256 / 3..(i + 1) * 256 / 3).contains(&(h as u32)));
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "k must be in [1, n]")]
+fn partitioned_hashes_too_many() {
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(10)
+        .build_hash_iter_hasher();
+    let _ = hasher.partitioned_hashes(&42, 11);
+}
+
+#[test]
+fn fuse_triple() {
+    let hasher = DoubleHashBuilder::<u32>::new().build_hash_iter_hasher();
+    let (len, segments) = (64, 100);
+    for key in 0..100 {
+        let [h0, h1, h2] = hasher.fuse_triple(&key, len, segments);
+        assert!(h0 < segments * len);
+        assert_eq!(h1 / len, h0 / len + 1);
+        assert_eq!(h2 / len, h0 / len + 2);
+
+        // Offsets are the regular sequence, modulo the segment length.
+        let offsets = DoubleHashBuilder::<u32>::new()
+            .with_n(len)
+            .build_hash_iter_hasher()
+            .hash_iter(&key, 3)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![h0 % len, h1 % len, h2 % len], offsets);
+    }
+
+    // Xor filter: one position in each third of the array.
+    for key in 0..100 {
+        let [h0, h1, h2] = hasher.fuse_triple(&key, 1000, 1);
+        assert!(h0 < 1000 && (1000..2000).contains(&h1) && (2000..3000).contains(&h2));
+    }
+}
+
+#[test]
+#[should_panic(expected = "fuse filter is too large")]
+fn fuse_triple_too_large() {
+    let hasher = DoubleHashBuilder::<u8>::new().build_hash_iter_hasher();
+    let _ = hasher.fuse_triple(&42, 16, 15);
+}
+
+#[test]
+fn ribbon_row() {
+    use std::collections::BTreeSet;
+
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    let mut patterns = BTreeSet::new();
+    for key in 0..100 {
+        let (start, coefficients) = hasher.ribbon_row(&key, 16);
+        assert!(start <= 1000 - 16);
+        assert_eq!(coefficients & 1, 1);
+        assert!(coefficients < 1 << 16);
+        assert_eq!(hasher.ribbon_row(&key, 16), (start, coefficients));
+        patterns.insert(coefficients);
+
+        // Narrower patterns are the low bits of the wider ones.
+        let (_, coefficients64) = hasher.ribbon_row(&key, 64);
+        assert_eq!(coefficients64 & 0xffff, coefficients);
+    }
+    assert!(patterns.len() > 90);
+
+    // The window may span the whole table.
+    let hasher = DoubleHashBuilder::<u32>::new()
+        .with_n(64)
+        .build_hash_iter_hasher();
+    assert_eq!(hasher.ribbon_row(&42, 64).0, 0);
+
+    // Full range.
+    let hasher = DoubleHashBuilder::<u8>::new()
+        .with_full_range()
+        .build_hash_iter_hasher();
+    for key in 0..100 {
+        assert!(hasher.ribbon_row(&key, 16).0 <= 240);
+        assert_eq!(hasher.ribbon_row(&key, 1).0, hasher.hashes(&key, 0).hash1());
+    }
+}
+
+#[test]
+#[should_panic(expected = "width must be in [1, 64]")]
+fn ribbon_row_too_wide() {
+    let hasher = DoubleHashBuilder::<u32>::new().build_hash_iter_hasher();
+    let _ = hasher.ribbon_row(&42, 65);
+}
+
+#[test]
+fn cuckoo() {
+    let hasher = DoubleHashHasher::<u64>::new();
+    let buckets = 1 << 10;
+    for key in 0..100 {
+        let cuckoo = hasher.cuckoo(&key, buckets, 12);
+        let fp = cuckoo.fingerprint();
+        assert!(fp > 0 && fp < 1 << 12);
+        assert!(cuckoo.index1() < buckets && cuckoo.index2() < buckets);
+
+        // Either bucket leads to the other one, given just the fingerprint.
+        assert_eq!(
+            CuckooHash::alt_index(cuckoo.index1(), fp, buckets),
+            cuckoo.index2()
+        );
+        assert_eq!(
+            CuckooHash::alt_index(cuckoo.index2(), fp, buckets),
+            cuckoo.index1()
+        );
+    }
+
+    // Keys sharing the fingerprint and the first bucket share both buckets.
+    let a = CuckooHash::new(5, 7 << 56, buckets, 8);
+    let b = CuckooHash::new(5 + buckets as u64, (7 << 56) | 0xff, buckets, 8);
+    assert_eq!(a, b);
+
+    // Zero fingerprints are reserved.
+    assert_eq!(CuckooHash::new(5, 0, buckets, 8).fingerprint(), 1);
+}
+
+#[test]
+#[should_panic(expected = "buckets must be a power of two")]
+fn cuckoo_buckets_not_power_of_two() {
+    let _ = DoubleHashHasher::<u64>::new().cuckoo(&42, 1000, 8);
+}
+
+#[test]
+fn hash_iter_quotients() {
+    let hasher = DoubleHashHasher::<u64>::new();
+    let hashes = hasher.hash_iter(&"hello", 3).collect::<Vec<_>>();
+
+    // Power-of-two table: the quotient is just the high bits.
+    let pairs = hasher
+        .hash_iter_quotients(&"hello", 3, 1 << 20, 8)
+        .collect::<Vec<_>>();
+    let expected = hashes
+        .iter()
+        .map(|h| (h >> 44, h & 0xff))
+        .collect::<Vec<_>>();
+    assert_eq!(pairs, expected);
+
+    // Other table sizes are scaled, not truncated.
+    let mut counts = [0; 3];
+    for key in 0..3000 {
+        for (quotient, remainder) in hasher.hash_iter_quotients(&key, 4, 3, 16) {
+            assert!(remainder < 1 << 16);
+            counts[quotient as usize] += 1;
+        }
+    }
+    assert!(
+        counts.iter().all(|&c| (3600..4400).contains(&c)),
+        "{counts:?}"
+    );
+}
+
+#[test]
+#[should_panic(expected = "table_size must be in [1, 2^(64 - remainder_bits)]")]
+fn hash_iter_quotients_table_too_large() {
+    let hasher = DoubleHashHasher::<u64>::new();
+    let _ = hasher.hash_iter_quotients(&42, 3, (1 << 56) + 1, 8);
+}
+
+#[test]
+fn hash_family() {
+    use std::hash::BuildHasher;
+
+    let family = HashFamily::<u32>::new().with_seed(42).with_n(1000);
+    let hashes = family.hash_iter(&"hello", 8).collect::<Vec<_>>();
+    assert_eq!(hashes.len(), 8);
+    assert!(hashes.iter().all(|&h| h < 1000));
+
+    // Each value comes from its own member, and does not depend on `count`.
+    for (i, &hash) in (0..).zip(&hashes) {
+        assert_eq!(family.hash(&"hello", i), hash);
+        assert_eq!(family.member(i).hash_one("hello") as u32 % 1000, hash);
+    }
+    assert_eq!(
+        family.hash_iter(&"hello", 3).collect::<Vec<_>>(),
+        hashes[..3]
+    );
+
+    // Members depend on the master seed.
+    let other = family
+        .with_seed(43)
+        .hash_iter(&"hello", 8)
+        .collect::<Vec<_>>();
+    assert_ne!(hashes, other);
+
+    // Full range: the hash values are not reduced.
+    let family = HashFamily::<u32>::new().with_seed(42).with_n(0);
+    for i in 0..8 {
+        assert_eq!(
+            family.hash(&"hello", i),
+            family.member(i).hash_one("hello") as u32
+        );
+    }
+}
+
+#[test]
+fn multiply_shift_backend() {
+    use std::{collections::BTreeSet, hash::BuildHasher};
+
+    let builder = MultiplyShiftBuilder::new().with_seed(42);
+    assert_eq!(builder.hash_one(7u64), builder.hash_one(7u64));
+    assert_ne!(
+        builder.hash_one(7u64),
+        MultiplyShiftBuilder::new().hash_one(7u64)
+    );
+
+    // Consecutive keys are spread over the whole range.
+    let hashes = (0..1000u64)
+        .map(|key| builder.hash_one(key))
+        .collect::<BTreeSet<_>>();
+    assert_eq!(hashes.len(), 1000);
+    assert!(hashes.iter().filter(|&&h| h >> 63 == 1).count() > 400);
+
+    // Usable as a drop-in replacement for the XXH3 hash builders.
+    let hasher = DoubleHashHasher::with_hash_builders(
+        MultiplyShiftBuilder::new().with_seed(1),
+        MultiplyShiftBuilder::new().with_seed(2),
+        1000u32,
+    );
+    for key in 0..100u32 {
+        let hashes = hasher.hash_iter(&key, 5).collect::<Vec<_>>();
+        assert!(hashes.iter().all(|&h| h < 1000));
+        assert_eq!(
+            hashes[0],
+            MultiplyShiftBuilder::new().with_seed(1).hash_one(key) as u32 % 1000
+        );
+    }
+}
+
+#[test]
+fn tabulation_backend() {
+    use std::{collections::BTreeSet, hash::BuildHasher};
+
+    let builder = TabulationBuilder::new().with_seed(42);
+    assert_eq!(builder.hash_one(7u64), builder.clone().hash_one(7u64));
+    assert_ne!(
+        builder.hash_one(7u64),
+        TabulationBuilder::new().hash_one(7u64)
+    );
+
+    // Single-word keys are hashed by XORing one table entry per byte, so
+    // keys differing in one byte differ by the XOR of two entries.
+    let (a, b, c) = (0x0100u64, 0x0200, 0x0201);
+    assert_eq!(
+        builder.hash_one(a) ^ builder.hash_one(b),
+        builder.hash_one(a | 1) ^ builder.hash_one(c)
+    );
+
+    // Consecutive keys are spread over the whole range.
+    let hashes = (0..1000u64)
+        .map(|key| builder.hash_one(key))
+        .collect::<BTreeSet<_>>();
+    assert_eq!(hashes.len(), 1000);
+    assert!(hashes.iter().filter(|&&h| h >> 63 == 1).count() > 400);
+
+    // Longer keys are chained word by word.
+    assert_ne!(
+        builder.hash_one((1u64, 2u64)),
+        builder.hash_one((2u64, 1u64))
+    );
+
+    let hasher = DoubleHashHasher::with_tabulation(42, 1000u32);
+    for key in 0..100u32 {
+        assert!(hasher.hash_iter(&key, 5).all(|h| h < 1000));
+    }
+}
+
+#[test]
+fn lemire_reduction() {
+    use {std::hash::BuildHasher, xxhash_rust::xxh3::Xxh3Builder};
+
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_n(1000)
+        .with_reduction(Reduction::Lemire)
+        .build_hash_iter_hasher();
+    for key in 0..100 {
+        // Start points are scaled into `[0, n)`, using their high bits.
+        let hash1 = Xxh3Builder::new().with_seed(12345).hash_one(key);
+        let hash2 = Xxh3Builder::new().with_seed(67890).hash_one(key);
+        let x = ((hash1 as u128 * 1000) >> 64) as u64;
+        let y = ((hash2 as u128 * 1000) >> 64) as u64;
+
+        let expected = Hashes::new(x, y, 1000, 10).collect::<Vec<_>>();
+        assert_eq!(hasher.hash_iter(&key, 10).collect::<Vec<_>>(), expected);
+    }
+
+    // Narrow types scale by their own width.
+    let hasher = DoubleHashBuilder::<u16>::new()
+        .with_n(1000)
+        .with_reduction(Reduction::Lemire)
+        .build_hash_iter_hasher();
+    let hash1 = Xxh3Builder::new().with_seed(12345).hash_one("hello") as u16;
+    assert_eq!(
+        hasher.hash_iter(&"hello", 1).next(),
+        Some(((hash1 as u32 * 1000) >> 16) as u16)
+    );
+
+    // Reduction does not depend on the low bits only.
+    let mut counts = [0; 3];
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_n(3)
+        .with_reduction(Reduction::Lemire)
+        .build_hash_iter_hasher();
+    for key in 0..3000 {
+        for hash in hasher.hash_iter(&key, 4) {
+            counts[hash as usize] += 1;
+        }
+    }
+    assert!(
+        counts.iter().all(|&c| (3600..4400).contains(&c)),
+        "{counts:?}"
+    );
+}
+
+#[test]
+fn bitmask_reduction() {
+    use {
+        std::{collections::BTreeSet, hash::BuildHasher},
+        xxhash_rust::xxh3::Xxh3Builder,
+    };
+
+    let builder = DoubleHashBuilder::<u64>::new()
+        .with_n(1024)
+        .with_reduction(Reduction::BitMask);
+    let hasher = builder.build_hash_iter_hasher();
+    let plain = builder
+        .with_hashing_mode(HashingMode::Plain)
+        .build_hash_iter_hasher();
+    for key in 0..100 {
+        let hash1 = Xxh3Builder::new().with_seed(12345).hash_one(key);
+        assert_eq!(hasher.hash_iter(&key, 1).next(), Some(hash1 & 1023));
+
+        // The second start point is odd, so the plain sequence visits every
+        // slot.
+        let slots = plain.hash_iter(&key, 1024).collect::<BTreeSet<_>>();
+        assert_eq!(slots.len(), 1024);
+    }
+
+    // Automatically picked for power-of-two `n` only.
+    for n in [1000, 1024] {
+        let auto = DoubleHashBuilder::<u64>::new()
+            .with_n(n)
+            .with_reduction(Reduction::Auto)
+            .build_hash_iter_hasher();
+        let expected = DoubleHashBuilder::<u64>::new()
+            .with_n(n)
+            .with_reduction(if n == 1024 {
+                Reduction::BitMask
+            } else {
+                Reduction::Modulo
+            })
+            .build_hash_iter_hasher();
+        for key in 0..100 {
+            assert!(auto.hash_iter(&key, 10).eq(expected.hash_iter(&key, 10)));
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "n must be a power of two for Reduction::BitMask")]
+fn bitmask_reduction_requires_power_of_two() {
+    let _ = DoubleHashBuilder::<u64>::new()
+        .with_n(1000)
+        .with_reduction(Reduction::BitMask)
+        .build_hash_iter_hasher();
+}
+
+#[test]
+fn bitmask_reduction_try_build() {
+    let builder = DoubleHashBuilder::<u64>::new().with_reduction(Reduction::BitMask);
+    assert_eq!(
+        builder.with_n(1000).try_build_hash_iter_hasher().err(),
+        Some(BuildError::BitMaskNotPowerOfTwo)
+    );
+    assert!(builder.with_n(1024).try_build_hash_iter_hasher().is_ok());
+    assert!(builder
+        .with_full_range()
+        .try_build_hash_iter_hasher()
+        .is_ok());
+}
+
+#[test]
+fn range_reduction() {
+    use hash_iter::{Identity, Lemire, Number, RangeReduction};
+
+    // Provided implementations can be selected on the builder directly.
+    let builder = DoubleHashBuilder::<u64>::new().with_n(1000);
+    let lemire = builder.with_reduction(Lemire).build_hash_iter_hasher();
+    let expected = builder
+        .with_reduction(Reduction::Lemire)
+        .build_hash_iter_hasher();
+    assert!(lemire
+        .hash_iter(&"hello", 10)
+        .eq(expected.hash_iter(&"hello", 10)));
+    assert!(lemire
+        .hashes_with_reduction(&"hello", 10, Lemire)
+        .eq(expected.hash_iter(&"hello", 10)));
+
+    // Identity leaves the start points as they are, if already in range.
+    let hasher = DoubleHashHasher::<u64>::new();
+    assert!(hasher
+        .hashes_with_reduction(&"hello", 10, Identity)
+        .eq(hasher.hash_iter(&"hello", 10)));
+
+    // Custom reduction: always start from the last slot.
+    struct Last;
+    impl RangeReduction for Last {
+        fn reduce<R: Number>(&self, _hash: R, n: R) -> R {
+            n - R::one()
+        }
+    }
+    let hashes = builder
+        .build_hash_iter_hasher()
+        .hashes_with_reduction(&"hello", 3, Last)
+        .collect::<Vec<_>>();
+    assert_eq!(hashes, vec![999, 998, 998]);
+}
+
+#[test]
+fn full_range() {
+    use {std::hash::BuildHasher, xxhash_rust::xxh3::Xxh3Builder};
+
+    let hasher = DoubleHashBuilder::<u64>::new()
+        .with_full_range()
+        .build_hash_iter_hasher();
+    let h1 = Xxh3Builder::new().with_seed(12345).hash_one("hello");
+    let h2 = Xxh3Builder::new().with_seed(67890).hash_one("hello");
+
+    // Plain wrapping arithmetic, modulo `2^64`.
+    let expected = (0..100u64)
+        .map(|i| {
+            let cubic = (i.wrapping_pow(3).wrapping_sub(i)) / 6;
+            h1.wrapping_add(i.wrapping_mul(h2)).wrapping_add(cubic)
+        })
+        .collect::<Vec<_>>();
+    let hashes = hasher.hashes(&"hello", 100);
+    assert_eq!(hashes.n(), 0);
+    assert_eq!(hashes.clone().collect::<Vec<_>>(), expected);
+
+    // Random access and iteration from the back agree.
+    assert_eq!(hashes.hash_at(77), expected[77]);
+    assert!(hashes.clone().rev().eq(expected.iter().rev().copied()));
+    assert_eq!(hashes.clone().nth(50), Some(expected[50]));
+
+    // Narrow types wrap at their own width.
+    let hasher = DoubleHashBuilder::<u8>::new()
+        .with_full_range()
+        .build_hash_iter_hasher();
+    let hashes = hasher.hash_iter(&"hello", 100).collect::<Vec<_>>();
+    let expected = Hashes::<u16>::new(h1 as u8 as u16, h2 as u8 as u16, 256, 100)
+        .map(|h| h as u8)
+        .collect::<Vec<_>>();
+    assert_eq!(hashes, expected);
 }
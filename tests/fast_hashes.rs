@@ -0,0 +1,42 @@
+#![cfg(all(feature = "rapidhash", feature = "wyhash"))]
+
+use {
+    hash_iter::{DoubleHashHasher, HashIterHasher, Hashes, RapidHashBuilder, WyHashBuilder},
+    std::hash::BuildHasher,
+};
+
+#[test]
+fn with_wyhash() {
+    let (builder1, builder2) = (
+        WyHashBuilder::new().with_seed(1),
+        WyHashBuilder::new().with_seed(2),
+    );
+    let (h1, h2) = (builder1.hash_one(7u64), builder2.hash_one(7u64));
+    let hasher = DoubleHashHasher::with_hash_builders(builder1, builder2, 1000u64);
+    assert!(hasher
+        .hash_iter(&7u64, 10)
+        .eq(Hashes::new(h1, h2, 1000, 10)));
+
+    let hasher = DoubleHashHasher::with_wyhash(42, 1000u64);
+    let other = DoubleHashHasher::with_wyhash(43, 1000u64);
+    assert!(hasher.hash_iter(&7u64, 10).ne(other.hash_iter(&7u64, 10)));
+
+    // The seed matters.
+    let builder = WyHashBuilder::new();
+    assert_ne!(builder.hash_one(7u64), builder.with_seed(1).hash_one(7u64));
+}
+
+#[test]
+fn with_rapidhash() {
+    let hasher = DoubleHashHasher::with_rapidhash(42, 1000u64);
+    let other = DoubleHashHasher::with_rapidhash(43, 1000u64);
+    assert!(hasher.hash_iter(&7u64, 10).ne(other.hash_iter(&7u64, 10)));
+
+    // Hash values are deterministic across runs.
+    let builder = RapidHashBuilder::new().with_seed(1);
+    assert_eq!(builder.hash_one(7u64), builder.hash_one(7u64));
+    assert_ne!(
+        builder.hash_one(7u64),
+        RapidHashBuilder::new().hash_one(7u64)
+    );
+}
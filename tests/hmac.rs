@@ -0,0 +1,74 @@
+#![cfg(feature = "hmac")]
+
+use {
+    hash_iter::{
+        DigestHalf,
+        DoubleHashHasher,
+        HashIterHasher,
+        Hashes,
+        HmacBuilder,
+        Number,
+        RawBytes,
+    },
+    std::hash::BuildHasher,
+};
+
+#[test]
+fn hmac_builder() {
+    // RFC 4231, test case 2: 5bdcc146bf60754e6a042426089575c7
+    // 5a003f089d2739839dec58b964ec3843.
+    let builder = HmacBuilder::new().with_secret_key(b"Jefe");
+    let data = RawBytes("what do ya want for nothing?");
+    assert_eq!(builder.hash_one(data), 0x5bdcc146bf60754e);
+    let builder = builder.with_half(DigestHalf::Second);
+    assert_eq!(builder.hash_one(data), 0x5a003f089d273983);
+
+    // The secret key is not leaked.
+    assert_eq!(format!("{builder:?}"), "HmacBuilder { half: Second, .. }");
+}
+
+#[test]
+fn with_hmac() {
+    let hasher = DoubleHashHasher::with_hmac(b"Jefe", 1000u64);
+    let data = RawBytes("what do ya want for nothing?");
+    let (h1, h2) = (0x5bdcc146bf60754e, 0x5a003f089d273983);
+    assert!(hasher
+        .hash_iter(&data, 10)
+        .eq(Hashes::new(h1, h2, 1000, 10)));
+
+    let other = DoubleHashHasher::with_hmac(b"Jeff", 1000u64);
+    assert!(other.hash_iter(&data, 10).ne(hasher.hash_iter(&data, 10)));
+}
+
+#[test]
+fn hmac_single_mac() {
+    // A single MAC per key provides both start points, matching the ones taken
+    // out of separate MACs with the two hash builders.
+    fn check<T: Number + PartialEq>(n: T) {
+        let hasher = DoubleHashHasher::with_hmac(b"Jefe", n);
+        let builder = HmacBuilder::new().with_secret_key(b"Jefe");
+        let two_macs = DoubleHashHasher::with_hash_builders(
+            builder.clone(),
+            builder.with_half(DigestHalf::Second),
+            n,
+        );
+        for key in ["abc", "hello", ""] {
+            assert!(hasher.hash_iter(&key, 10).eq(two_macs.hash_iter(&key, 10)));
+        }
+
+        let hasher = hasher.with_domain("user-ids");
+        let two_macs = two_macs.with_domain("user-ids");
+        assert!(hasher
+            .hash_iter(&"hello", 10)
+            .eq(two_macs.hash_iter(&"hello", 10)));
+        let mut stream = hasher.stream_hasher();
+        stream.write(b"hel");
+        stream.write(b"lo");
+        assert!(stream
+            .finish_iter(10)
+            .eq(two_macs.hash_iter(&RawBytes("hello"), 10)));
+    }
+
+    check(1000u64);
+    check(u128::MAX);
+}
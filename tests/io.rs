@@ -0,0 +1,65 @@
+#![cfg(feature = "io")]
+
+use {
+    hash_iter::{DoubleHashHasher, HashIterHasher, RawBytes},
+    std::io,
+};
+
+/// Reader returning its bytes a few at a time, interrupted before each read.
+struct Trickle<'a> {
+    bytes: &'a [u8],
+    interrupted: bool,
+}
+
+impl io::Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.interrupted = !self.interrupted;
+        if self.interrupted {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+        let len = self.bytes.len().min(buf.len()).min(7);
+        let (head, tail) = self.bytes.split_at(len);
+        buf[..len].copy_from_slice(head);
+        self.bytes = tail;
+        Ok(len)
+    }
+}
+
+#[test]
+fn hash_iter_reader() {
+    let hasher = DoubleHashHasher::<u64>::new();
+
+    // Larger than the buffer.
+    let key = (0..100_000).map(|i| i as u8).collect::<Vec<_>>();
+    for len in [0, 5, 1000, key.len()] {
+        let key = &key[..len];
+        let expected = hasher.hash_iter(&RawBytes(key), 10).collect::<Vec<_>>();
+        assert!(hasher
+            .hash_iter_reader(key, 10)
+            .unwrap()
+            .eq(expected.iter().copied()));
+        let trickle = Trickle {
+            bytes: key,
+            interrupted: false,
+        };
+        assert!(hasher
+            .hash_iter_reader(trickle, 10)
+            .unwrap()
+            .eq(expected.iter().copied()));
+    }
+}
+
+#[test]
+fn hash_iter_reader_error() {
+    struct Failing;
+
+    impl io::Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+    }
+
+    let hasher = DoubleHashHasher::<u64>::new();
+    let err = hasher.hash_iter_reader(Failing, 10).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+}
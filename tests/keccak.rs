@@ -0,0 +1,85 @@
+#![cfg(feature = "keccak")]
+
+use {
+    hash_iter::{
+        BuildHashIterHasher,
+        DigestHalf,
+        DoubleHashBuilder,
+        DoubleHashHasher,
+        HashIterHasher,
+        Hashes,
+        Keccak256Builder,
+        KeyHashing,
+        Number,
+        RawBytes,
+    },
+    std::hash::BuildHasher,
+};
+
+#[test]
+fn keccak256_builder() {
+    // Keccak-256("") = c5d2460186f7233c927e7db2dcc703c0
+    // e500b653ca82273b7bfad8045d85a470.
+    let builder = Keccak256Builder::new();
+    assert_eq!(builder.hash_one(RawBytes("")), 0xc5d2460186f7233c);
+    let builder = builder.with_half(DigestHalf::Second);
+    assert_eq!(builder.hash_one(RawBytes("")), 0xe500b653ca82273b);
+}
+
+#[test]
+fn with_keccak256() {
+    let hasher = DoubleHashHasher::with_keccak256(1000u64);
+    let (h1, h2) = (0xc5d2460186f7233c, 0xe500b653ca82273b);
+    assert!(hasher
+        .hash_iter(&RawBytes(""), 10)
+        .eq(Hashes::new(h1, h2, 1000, 10)));
+}
+
+#[test]
+fn keccak256_key_hashing() {
+    // A single digest per key provides both start points, matching the ones
+    // taken out of separate digests with the two hash builders.
+    fn check<T, H>(hasher: DoubleHashHasher<T, H>, n: T)
+    where
+        T: Number + PartialEq,
+        H: BuildHasher,
+        H::Hasher: Clone,
+    {
+        let builder = Keccak256Builder::new();
+        let two_digests =
+            DoubleHashHasher::with_hash_builders(builder, builder.with_half(DigestHalf::Second), n);
+        for key in ["abc", "hello", ""] {
+            assert!(hasher
+                .hash_iter(&key, 10)
+                .eq(two_digests.hash_iter(&key, 10)));
+        }
+
+        let two_digests = two_digests.with_domain("user-ids");
+        let hasher = hasher.with_domain("user-ids");
+        assert!(hasher
+            .hash_iter(&"hello", 10)
+            .eq(two_digests.hash_iter(&"hello", 10)));
+        let mut stream = hasher.stream_hasher();
+        stream.write(b"hel");
+        stream.write(b"lo");
+        assert!(stream
+            .finish_iter(10)
+            .eq(two_digests.hash_iter(&RawBytes("hello"), 10)));
+    }
+
+    check(DoubleHashHasher::with_keccak256(1000u64), 1000);
+    check(
+        DoubleHashBuilder::<u64>::new()
+            .with_n(1000)
+            .with_key_hashing(KeyHashing::Keccak256)
+            .build_hash_iter_hasher(),
+        1000,
+    );
+    check(
+        DoubleHashBuilder::<u128>::new()
+            .with_n(u128::MAX)
+            .with_key_hashing(KeyHashing::Keccak256)
+            .build_hash_iter_hasher(),
+        u128::MAX,
+    );
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "murmur3")]
+
+use {
+    hash_iter::{
+        DoubleHashHasher,
+        HashIterHasher,
+        Hashes,
+        Murmur3Builder,
+        Murmur3Variant,
+        RawBytes,
+    },
+    std::hash::BuildHasher,
+};
+
+#[test]
+fn murmur3_builder() {
+    // Reference values (the x64_128 digest is df65d6d2d12d51f1 64c5f3a85066322c).
+    let key = RawBytes("Hello, world!");
+    let builder = Murmur3Builder::new().with_variant(Murmur3Variant::X86_32);
+    assert_eq!(builder.hash_one(key), 0xc0363e43);
+    let builder = Murmur3Builder::new().with_variant(Murmur3Variant::X64_128Low);
+    assert_eq!(builder.hash_one(key), 0xf1512dd1d2d665df);
+    let builder = Murmur3Builder::new().with_variant(Murmur3Variant::X64_128High);
+    assert_eq!(builder.hash_one(key), 0x2c326650a8f3c564);
+
+    // Bytes may be written in several parts.
+    let mut bytes = b"Hello, world!".to_vec();
+    assert_eq!(
+        builder.hash_one(RawBytes(&bytes)),
+        builder.hash_one((RawBytes(&bytes[..5]), RawBytes(&bytes[5..])))
+    );
+
+    // The seed is passed through.
+    bytes.clear();
+    let digest = murmur3::murmur3_x64_128(&mut &bytes[..], 1).unwrap();
+    let builder = builder.with_seed(1);
+    assert_eq!(builder.hash_one(RawBytes(&bytes)), (digest >> 64) as u64);
+}
+
+#[test]
+fn with_murmur3() {
+    let key = RawBytes(b"Hello, world!");
+    let hasher = DoubleHashHasher::with_murmur3(0, 1000u64);
+    let (h1, h2) = (0xf1512dd1d2d665df, 0x2c326650a8f3c564);
+    assert!(hasher.hash_iter(&key, 10).eq(Hashes::new(h1, h2, 1000, 10)));
+}
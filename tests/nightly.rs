@@ -0,0 +1,65 @@
+#![cfg(feature = "nightly")]
+#![feature(trusted_len)]
+
+use {hash_iter::Hashes, std::iter::TrustedLen};
+
+#[test]
+fn trusted_len() {
+    fn assert_trusted_len<I: TrustedLen>(_: &I) {}
+
+    let iter = Hashes::new(12345u64, 67890, 1000, 100);
+    assert_trusted_len(&iter);
+
+    let hashes = iter.collect::<Vec<_>>();
+    assert_eq!(hashes.len(), 100);
+    assert_eq!(hashes.capacity(), 100);
+}
+
+#[test]
+fn simd_hash_iter_batch() {
+    use hash_iter::{
+        BuildHashIterHasher,
+        DoubleHashBuilder,
+        DoubleHashHasher,
+        HashIterHasher,
+        HashingMode,
+    };
+
+    fn check<T: hash_iter::Number<Repr = u64> + Default + PartialEq>(hasher: DoubleHashHasher<T>) {
+        // Number of keys not a multiple of lane count, to cover the tail.
+        let keys = (0..103).collect::<Vec<u32>>();
+        for count in [0, 1, 7, 100] {
+            let mut expected = vec![T::default(); keys.len() * count];
+            for (key, row) in keys.iter().zip(expected.chunks_exact_mut(count.max(1))) {
+                hasher.hash_iter_into(key, row);
+            }
+            let mut out = vec![T::default(); keys.len() * count];
+            hasher.simd_hash_iter_batch(&keys, count, &mut out);
+            assert!(out == expected);
+        }
+    }
+
+    check(DoubleHashHasher::new());
+    check(
+        DoubleHashBuilder::<u64>::new()
+            .with_n(10)
+            .build_hash_iter_hasher(),
+    );
+    check(
+        DoubleHashBuilder::<u64>::new()
+            .with_n(u64::MAX)
+            .build_hash_iter_hasher(),
+    );
+    check(DoubleHashBuilder::<usize>::new().build_hash_iter_hasher());
+    check(
+        DoubleHashBuilder::<i64>::new()
+            .with_n(1 << 40)
+            .build_hash_iter_hasher(),
+    );
+    check(DoubleHashHasher::new().with_hashing_mode(HashingMode::Plain));
+    check(
+        DoubleHashBuilder::<u64>::new()
+            .with_full_range()
+            .build_hash_iter_hasher(),
+    );
+}
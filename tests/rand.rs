@@ -0,0 +1,73 @@
+#![cfg(feature = "rand")]
+
+use hash_iter::{BuildHashIterHasher, DoubleHashBuilder, DoubleHashHasher, HashIterHasher};
+
+#[test]
+fn random() {
+    // Builders of the same process agree.
+    let hasher = DoubleHashBuilder::<u64>::random().build_hash_iter_hasher();
+    let other = DoubleHashBuilder::<u64>::random().build_hash_iter_hasher();
+    assert!(hasher
+        .hash_iter(&"hello", 10)
+        .eq(other.hash_iter(&"hello", 10)));
+
+    // The seeds are not the default ones.
+    assert!(!hasher
+        .hash_iter(&"hello", 10)
+        .eq(DoubleHashHasher::new().hash_iter(&"hello", 10)));
+
+    // The rest of the configuration is kept.
+    let hasher = DoubleHashBuilder::<u32>::random()
+        .with_n(1000)
+        .build_hash_iter_hasher();
+    assert!(hasher.hash_iter(&"hello", 100).all(|h| h < 1000));
+}
+
+#[test]
+fn from_rng() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let hasher = |seed| {
+        let mut rng = StdRng::seed_from_u64(seed);
+        DoubleHashBuilder::<u64>::from_rng(&mut rng)
+            .with_n(1000)
+            .build_hash_iter_hasher()
+    };
+    // Reproducible with a seeded RNG.
+    assert!(hasher(1)
+        .hash_iter(&"hello", 10)
+        .eq(hasher(1).hash_iter(&"hello", 10)));
+    assert!(!hasher(1)
+        .hash_iter(&"hello", 10)
+        .eq(hasher(2).hash_iter(&"hello", 10)));
+
+    // Same as drawing the seeds by hand.
+    let mut rng = StdRng::seed_from_u64(1);
+    let (seed1, seed2) = (
+        rand::RngCore::next_u64(&mut rng),
+        rand::RngCore::next_u64(&mut rng),
+    );
+    assert!(hasher(1)
+        .hash_iter(&"hello", 10)
+        .eq(DoubleHashHasher::with_seeds_and_n(seed1, seed2, 1000u64).hash_iter(&"hello", 10)));
+
+    // The secret changes the sequences of short keys, as well as long ones,
+    // and differs from one draw to the next.
+    let mut rng = StdRng::seed_from_u64(1);
+    let builder = DoubleHashBuilder::<u64>::from_rng(&mut rng).with_n(1000);
+    let with_secret = builder
+        .with_secret_from_rng(&mut rng)
+        .build_hash_iter_hasher();
+    let rotated = builder
+        .with_secret_from_rng(&mut rng)
+        .build_hash_iter_hasher();
+    let without_secret = builder.build_hash_iter_hasher();
+    for key in [&b"hello"[..], &[7u8; 256]] {
+        assert!(!with_secret
+            .hash_iter(&key, 10)
+            .eq(without_secret.hash_iter(&key, 10)));
+        assert!(!with_secret
+            .hash_iter(&key, 10)
+            .eq(rotated.hash_iter(&key, 10)));
+    }
+}
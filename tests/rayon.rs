@@ -0,0 +1,24 @@
+#![cfg(feature = "rayon")]
+
+use hash_iter::{DoubleHashHasher, HashIterHasher};
+
+#[test]
+fn par_hash_iter_batch() {
+    let hasher = DoubleHashHasher::new();
+    let keys = (0..10_000).collect::<Vec<u32>>();
+
+    let mut expected = vec![0; keys.len() * 7];
+    hasher.hash_iter_batch(&keys, 7, &mut expected);
+    let mut out = vec![0; keys.len() * 7];
+    hasher.par_hash_iter_batch(&keys, 7, &mut out);
+    assert_eq!(out, expected);
+
+    hasher.par_hash_iter_batch(&keys, 0, &mut []);
+}
+
+#[test]
+#[should_panic(expected = "out must hold count hashes per key")]
+fn par_hash_iter_batch_wrong_len() {
+    let mut out = [0; 5];
+    DoubleHashHasher::new().par_hash_iter_batch(&["hello", "world"], 3, &mut out);
+}
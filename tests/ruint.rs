@@ -0,0 +1,32 @@
+#![cfg(feature = "ruint")]
+
+use {
+    hash_iter::{BuildHashIterHasher, DoubleHashBuilder, HashIterHasher},
+    ruint::aliases::U256,
+};
+
+#[test]
+fn u256_hashes() {
+    let hasher = DoubleHashBuilder::<U256>::new()
+        .with_n(U256::MAX)
+        .build_hash_iter_hasher();
+
+    let hashes = hasher.hash_iter(&"hello", 10).collect::<Vec<_>>();
+    assert_eq!(hashes.len(), 10);
+
+    // Starting points cover the whole 256-bit keyspace, not just the low 64 bits.
+    assert!(hashes.iter().any(|h| h.bit_len() > 192));
+
+    // Deterministic for the same key.
+    assert_eq!(hashes, hasher.hash_iter(&"hello", 10).collect::<Vec<_>>());
+}
+
+#[test]
+fn u256_custom_n() {
+    let n = U256::from(1u64) << 200;
+    let hasher = DoubleHashBuilder::<U256>::new()
+        .with_n(n)
+        .build_hash_iter_hasher();
+
+    assert!(hasher.hash_iter(&"hello", 100).all(|h| h < n));
+}
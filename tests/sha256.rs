@@ -0,0 +1,85 @@
+#![cfg(feature = "sha2")]
+
+use {
+    hash_iter::{
+        BuildHashIterHasher,
+        DigestHalf,
+        DoubleHashBuilder,
+        DoubleHashHasher,
+        HashIterHasher,
+        Hashes,
+        KeyHashing,
+        Number,
+        RawBytes,
+        Sha256Builder,
+    },
+    std::hash::BuildHasher,
+};
+
+#[test]
+fn sha256_builder() {
+    // SHA-256("abc") = ba7816bf8f01cfea414140de5dae2223
+    // b00361a396177a9cb410ff61f20015ad.
+    let builder = Sha256Builder::new();
+    assert_eq!(builder.hash_one(RawBytes("abc")), 0xba7816bf8f01cfea);
+    let builder = builder.with_half(DigestHalf::Second);
+    assert_eq!(builder.hash_one(RawBytes("abc")), 0xb00361a396177a9c);
+}
+
+#[test]
+fn with_sha256() {
+    let hasher = DoubleHashHasher::with_sha256(1000u64);
+    let (h1, h2) = (0xba7816bf8f01cfea, 0xb00361a396177a9c);
+    assert!(hasher
+        .hash_iter(&RawBytes("abc"), 10)
+        .eq(Hashes::new(h1, h2, 1000, 10)));
+}
+
+#[test]
+fn sha256_key_hashing() {
+    // A single digest per key provides both start points, matching the ones
+    // taken out of separate digests with the two hash builders.
+    fn check<T, H>(hasher: DoubleHashHasher<T, H>, n: T)
+    where
+        T: Number + PartialEq,
+        H: BuildHasher,
+        H::Hasher: Clone,
+    {
+        let builder = Sha256Builder::new();
+        let two_digests =
+            DoubleHashHasher::with_hash_builders(builder, builder.with_half(DigestHalf::Second), n);
+        for key in ["abc", "hello", ""] {
+            assert!(hasher
+                .hash_iter(&key, 10)
+                .eq(two_digests.hash_iter(&key, 10)));
+        }
+
+        let two_digests = two_digests.with_domain("user-ids");
+        let hasher = hasher.with_domain("user-ids");
+        assert!(hasher
+            .hash_iter(&"hello", 10)
+            .eq(two_digests.hash_iter(&"hello", 10)));
+        let mut stream = hasher.stream_hasher();
+        stream.write(b"hel");
+        stream.write(b"lo");
+        assert!(stream
+            .finish_iter(10)
+            .eq(two_digests.hash_iter(&RawBytes("hello"), 10)));
+    }
+
+    check(DoubleHashHasher::with_sha256(1000u64), 1000);
+    check(
+        DoubleHashBuilder::<u64>::new()
+            .with_n(1000)
+            .with_key_hashing(KeyHashing::Sha256)
+            .build_hash_iter_hasher(),
+        1000,
+    );
+    check(
+        DoubleHashBuilder::<u128>::new()
+            .with_n(u128::MAX)
+            .with_key_hashing(KeyHashing::Sha256)
+            .build_hash_iter_hasher(),
+        u128::MAX,
+    );
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "siphash")]
+
+use {
+    hash_iter::{DoubleHashHasher, HashIterHasher, Hashes, SipHashBuilder, SipRounds},
+    siphasher::sip::{SipHasher13, SipHasher24},
+    std::hash::{BuildHasher, Hash, Hasher},
+};
+
+#[test]
+fn siphash_builder() {
+    let key = 0x0f0e_0d0c_0b0a_0908_0706_0504_0302_0100u128;
+    let (k0, k1) = (0x0706_0504_0302_0100, 0x0f0e_0d0c_0b0a_0908);
+
+    let mut expected = SipHasher13::new_with_keys(k0, k1);
+    "hello".hash(&mut expected);
+    assert_eq!(
+        SipHashBuilder::new(key).hash_one("hello"),
+        expected.finish()
+    );
+
+    let mut expected = SipHasher24::new_with_keys(k0, k1);
+    "hello".hash(&mut expected);
+    let builder = SipHashBuilder::new(key).with_rounds(SipRounds::Sip24);
+    assert_eq!(builder.hash_one("hello"), expected.finish());
+
+    // The key is not leaked.
+    assert_eq!(
+        format!("{builder:?}"),
+        "SipHashBuilder { rounds: Sip24, .. }"
+    );
+}
+
+#[test]
+fn with_siphash() {
+    let (key1, key2) = (1, 2);
+    let hasher = DoubleHashHasher::with_siphash(key1, key2, 1000u64);
+    let (h1, h2) = (
+        SipHashBuilder::new(key1).hash_one("hello"),
+        SipHashBuilder::new(key2).hash_one("hello"),
+    );
+    assert!(hasher
+        .hash_iter(&"hello", 10)
+        .eq(Hashes::new(h1, h2, 1000, 10)));
+
+    // Different keys, different sequences.
+    let other = DoubleHashHasher::with_siphash(key1, key2 + 1, 1000u64);
+    assert!(other
+        .hash_iter(&"hello", 10)
+        .ne(hasher.hash_iter(&"hello", 10)));
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn secure() {
+    // Keys are drawn once per process.
+    let hasher = DoubleHashHasher::secure(usize::MAX as u64);
+    assert!(hasher
+        .hash_iter(&"hello", 10)
+        .eq(DoubleHashHasher::secure(usize::MAX as u64).hash_iter(&"hello", 10)));
+
+    let hasher = DoubleHashHasher::with_siphash(0, 1, usize::MAX as u64);
+    assert!(!hasher
+        .hash_iter(&"hello", 10)
+        .eq(DoubleHashHasher::secure(usize::MAX as u64).hash_iter(&"hello", 10)));
+
+    // For a real table size, `h2` is non-degenerate modulo that size.
+    let hasher = DoubleHashHasher::secure(1024u64);
+    for key in 0..1000 {
+        let hashes = hasher.hashes(&key, 100);
+        assert_eq!(hashes.hash2() % 2, 1);
+        assert!(hashes.clone().all(|h| h < 1024));
+        assert!(hasher.hash_iter(&key, 100).eq(hashes));
+    }
+    let hasher = DoubleHashHasher::secure(1000u32);
+    for key in 0..1000 {
+        let mut hashes = hasher.hashes(&key, 100);
+        assert!((1..1000).contains(&hashes.hash2()));
+        assert!(hashes.all(|h| h < 1000));
+    }
+}
@@ -0,0 +1,17 @@
+#![cfg(feature = "smallvec")]
+
+use hash_iter::{DoubleHashHasher, HashIterHasher};
+
+#[test]
+fn hash_iter_smallvec() {
+    let hasher = DoubleHashHasher::new();
+    let hashes = hasher.hash_iter(&"hello", 32).collect::<Vec<_>>();
+
+    let small = hasher.hash_iter_smallvec::<16, _>(&"hello", 7);
+    assert!(!small.spilled());
+    assert_eq!(small[..], hashes[..7]);
+
+    let large = hasher.hash_iter_smallvec::<16, _>(&"hello", 32);
+    assert!(large.spilled());
+    assert_eq!(large[..], hashes[..]);
+}
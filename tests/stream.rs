@@ -0,0 +1,18 @@
+#![cfg(feature = "futures")]
+
+use {
+    futures::{executor::block_on, stream, StreamExt},
+    hash_iter::{DoubleHashHasher, HashIterHasher},
+};
+
+#[test]
+fn hash_stream() {
+    let hasher = DoubleHashHasher::new();
+    let keys = stream::iter(["hello", "world", "foo"]);
+    let hashed = block_on(hasher.hash_stream(keys, 3).collect::<Vec<_>>());
+
+    assert_eq!(hashed.len(), 3);
+    for (key, hashes) in hashed {
+        assert_eq!(hashes, hasher.hash_iter(&key, 3).collect::<Vec<_>>());
+    }
+}